@@ -0,0 +1,2008 @@
+pragma solidity ^0.8.0;
+
+contract Large {
+    uint256 public total;
+    mapping(address => uint256) public balances;
+    mapping(address => mapping(uint256 => bool)) approvals;
+    event Updated(address who, uint256 amount);
+
+    function update0(address who, uint256 amount) public returns (uint256) {
+        require(amount > 0, "amount too small 0");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][0] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update1(address who, uint256 amount) public returns (uint256) {
+        require(amount > 1, "amount too small 1");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][1] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update2(address who, uint256 amount) public returns (uint256) {
+        require(amount > 2, "amount too small 2");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][2] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update3(address who, uint256 amount) public returns (uint256) {
+        require(amount > 3, "amount too small 3");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][3] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update4(address who, uint256 amount) public returns (uint256) {
+        require(amount > 4, "amount too small 4");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][4] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update5(address who, uint256 amount) public returns (uint256) {
+        require(amount > 5, "amount too small 5");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][5] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update6(address who, uint256 amount) public returns (uint256) {
+        require(amount > 6, "amount too small 6");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][6] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update7(address who, uint256 amount) public returns (uint256) {
+        require(amount > 7, "amount too small 7");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][7] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update8(address who, uint256 amount) public returns (uint256) {
+        require(amount > 8, "amount too small 8");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][8] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update9(address who, uint256 amount) public returns (uint256) {
+        require(amount > 9, "amount too small 9");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][9] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update10(address who, uint256 amount) public returns (uint256) {
+        require(amount > 10, "amount too small 10");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][10] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update11(address who, uint256 amount) public returns (uint256) {
+        require(amount > 11, "amount too small 11");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][11] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update12(address who, uint256 amount) public returns (uint256) {
+        require(amount > 12, "amount too small 12");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][12] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update13(address who, uint256 amount) public returns (uint256) {
+        require(amount > 13, "amount too small 13");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][13] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update14(address who, uint256 amount) public returns (uint256) {
+        require(amount > 14, "amount too small 14");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][14] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update15(address who, uint256 amount) public returns (uint256) {
+        require(amount > 15, "amount too small 15");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][15] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update16(address who, uint256 amount) public returns (uint256) {
+        require(amount > 16, "amount too small 16");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][16] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update17(address who, uint256 amount) public returns (uint256) {
+        require(amount > 17, "amount too small 17");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][17] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update18(address who, uint256 amount) public returns (uint256) {
+        require(amount > 18, "amount too small 18");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][18] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update19(address who, uint256 amount) public returns (uint256) {
+        require(amount > 19, "amount too small 19");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][19] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update20(address who, uint256 amount) public returns (uint256) {
+        require(amount > 20, "amount too small 20");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][20] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update21(address who, uint256 amount) public returns (uint256) {
+        require(amount > 21, "amount too small 21");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][21] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update22(address who, uint256 amount) public returns (uint256) {
+        require(amount > 22, "amount too small 22");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][22] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update23(address who, uint256 amount) public returns (uint256) {
+        require(amount > 23, "amount too small 23");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][23] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update24(address who, uint256 amount) public returns (uint256) {
+        require(amount > 24, "amount too small 24");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][24] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update25(address who, uint256 amount) public returns (uint256) {
+        require(amount > 25, "amount too small 25");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][25] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update26(address who, uint256 amount) public returns (uint256) {
+        require(amount > 26, "amount too small 26");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][26] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update27(address who, uint256 amount) public returns (uint256) {
+        require(amount > 27, "amount too small 27");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][27] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update28(address who, uint256 amount) public returns (uint256) {
+        require(amount > 28, "amount too small 28");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][28] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update29(address who, uint256 amount) public returns (uint256) {
+        require(amount > 29, "amount too small 29");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][29] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update30(address who, uint256 amount) public returns (uint256) {
+        require(amount > 30, "amount too small 30");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][30] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update31(address who, uint256 amount) public returns (uint256) {
+        require(amount > 31, "amount too small 31");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][31] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update32(address who, uint256 amount) public returns (uint256) {
+        require(amount > 32, "amount too small 32");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][32] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update33(address who, uint256 amount) public returns (uint256) {
+        require(amount > 33, "amount too small 33");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][33] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update34(address who, uint256 amount) public returns (uint256) {
+        require(amount > 34, "amount too small 34");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][34] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update35(address who, uint256 amount) public returns (uint256) {
+        require(amount > 35, "amount too small 35");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][35] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update36(address who, uint256 amount) public returns (uint256) {
+        require(amount > 36, "amount too small 36");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][36] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update37(address who, uint256 amount) public returns (uint256) {
+        require(amount > 37, "amount too small 37");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][37] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update38(address who, uint256 amount) public returns (uint256) {
+        require(amount > 38, "amount too small 38");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][38] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update39(address who, uint256 amount) public returns (uint256) {
+        require(amount > 39, "amount too small 39");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][39] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update40(address who, uint256 amount) public returns (uint256) {
+        require(amount > 40, "amount too small 40");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][40] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update41(address who, uint256 amount) public returns (uint256) {
+        require(amount > 41, "amount too small 41");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][41] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update42(address who, uint256 amount) public returns (uint256) {
+        require(amount > 42, "amount too small 42");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][42] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update43(address who, uint256 amount) public returns (uint256) {
+        require(amount > 43, "amount too small 43");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][43] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update44(address who, uint256 amount) public returns (uint256) {
+        require(amount > 44, "amount too small 44");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][44] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update45(address who, uint256 amount) public returns (uint256) {
+        require(amount > 45, "amount too small 45");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][45] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update46(address who, uint256 amount) public returns (uint256) {
+        require(amount > 46, "amount too small 46");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][46] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update47(address who, uint256 amount) public returns (uint256) {
+        require(amount > 47, "amount too small 47");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][47] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update48(address who, uint256 amount) public returns (uint256) {
+        require(amount > 48, "amount too small 48");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][48] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update49(address who, uint256 amount) public returns (uint256) {
+        require(amount > 49, "amount too small 49");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][49] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update50(address who, uint256 amount) public returns (uint256) {
+        require(amount > 50, "amount too small 50");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][50] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update51(address who, uint256 amount) public returns (uint256) {
+        require(amount > 51, "amount too small 51");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][51] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update52(address who, uint256 amount) public returns (uint256) {
+        require(amount > 52, "amount too small 52");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][52] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update53(address who, uint256 amount) public returns (uint256) {
+        require(amount > 53, "amount too small 53");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][53] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update54(address who, uint256 amount) public returns (uint256) {
+        require(amount > 54, "amount too small 54");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][54] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update55(address who, uint256 amount) public returns (uint256) {
+        require(amount > 55, "amount too small 55");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][55] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update56(address who, uint256 amount) public returns (uint256) {
+        require(amount > 56, "amount too small 56");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][56] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update57(address who, uint256 amount) public returns (uint256) {
+        require(amount > 57, "amount too small 57");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][57] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update58(address who, uint256 amount) public returns (uint256) {
+        require(amount > 58, "amount too small 58");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][58] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update59(address who, uint256 amount) public returns (uint256) {
+        require(amount > 59, "amount too small 59");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][59] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update60(address who, uint256 amount) public returns (uint256) {
+        require(amount > 60, "amount too small 60");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][60] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update61(address who, uint256 amount) public returns (uint256) {
+        require(amount > 61, "amount too small 61");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][61] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update62(address who, uint256 amount) public returns (uint256) {
+        require(amount > 62, "amount too small 62");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][62] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update63(address who, uint256 amount) public returns (uint256) {
+        require(amount > 63, "amount too small 63");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][63] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update64(address who, uint256 amount) public returns (uint256) {
+        require(amount > 64, "amount too small 64");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][64] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update65(address who, uint256 amount) public returns (uint256) {
+        require(amount > 65, "amount too small 65");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][65] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update66(address who, uint256 amount) public returns (uint256) {
+        require(amount > 66, "amount too small 66");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][66] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update67(address who, uint256 amount) public returns (uint256) {
+        require(amount > 67, "amount too small 67");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][67] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update68(address who, uint256 amount) public returns (uint256) {
+        require(amount > 68, "amount too small 68");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][68] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update69(address who, uint256 amount) public returns (uint256) {
+        require(amount > 69, "amount too small 69");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][69] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update70(address who, uint256 amount) public returns (uint256) {
+        require(amount > 70, "amount too small 70");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][70] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update71(address who, uint256 amount) public returns (uint256) {
+        require(amount > 71, "amount too small 71");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][71] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update72(address who, uint256 amount) public returns (uint256) {
+        require(amount > 72, "amount too small 72");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][72] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update73(address who, uint256 amount) public returns (uint256) {
+        require(amount > 73, "amount too small 73");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][73] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update74(address who, uint256 amount) public returns (uint256) {
+        require(amount > 74, "amount too small 74");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][74] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update75(address who, uint256 amount) public returns (uint256) {
+        require(amount > 75, "amount too small 75");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][75] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update76(address who, uint256 amount) public returns (uint256) {
+        require(amount > 76, "amount too small 76");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][76] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update77(address who, uint256 amount) public returns (uint256) {
+        require(amount > 77, "amount too small 77");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][77] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update78(address who, uint256 amount) public returns (uint256) {
+        require(amount > 78, "amount too small 78");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][78] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update79(address who, uint256 amount) public returns (uint256) {
+        require(amount > 79, "amount too small 79");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][79] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update80(address who, uint256 amount) public returns (uint256) {
+        require(amount > 80, "amount too small 80");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][80] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update81(address who, uint256 amount) public returns (uint256) {
+        require(amount > 81, "amount too small 81");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][81] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update82(address who, uint256 amount) public returns (uint256) {
+        require(amount > 82, "amount too small 82");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][82] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update83(address who, uint256 amount) public returns (uint256) {
+        require(amount > 83, "amount too small 83");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][83] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update84(address who, uint256 amount) public returns (uint256) {
+        require(amount > 84, "amount too small 84");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][84] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update85(address who, uint256 amount) public returns (uint256) {
+        require(amount > 85, "amount too small 85");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][85] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update86(address who, uint256 amount) public returns (uint256) {
+        require(amount > 86, "amount too small 86");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][86] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update87(address who, uint256 amount) public returns (uint256) {
+        require(amount > 87, "amount too small 87");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][87] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update88(address who, uint256 amount) public returns (uint256) {
+        require(amount > 88, "amount too small 88");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][88] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update89(address who, uint256 amount) public returns (uint256) {
+        require(amount > 89, "amount too small 89");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][89] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update90(address who, uint256 amount) public returns (uint256) {
+        require(amount > 90, "amount too small 90");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][90] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update91(address who, uint256 amount) public returns (uint256) {
+        require(amount > 91, "amount too small 91");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][91] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update92(address who, uint256 amount) public returns (uint256) {
+        require(amount > 92, "amount too small 92");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][92] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update93(address who, uint256 amount) public returns (uint256) {
+        require(amount > 93, "amount too small 93");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][93] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update94(address who, uint256 amount) public returns (uint256) {
+        require(amount > 94, "amount too small 94");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][94] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update95(address who, uint256 amount) public returns (uint256) {
+        require(amount > 95, "amount too small 95");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][95] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update96(address who, uint256 amount) public returns (uint256) {
+        require(amount > 96, "amount too small 96");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][96] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update97(address who, uint256 amount) public returns (uint256) {
+        require(amount > 97, "amount too small 97");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][97] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update98(address who, uint256 amount) public returns (uint256) {
+        require(amount > 98, "amount too small 98");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][98] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update99(address who, uint256 amount) public returns (uint256) {
+        require(amount > 99, "amount too small 99");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][99] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update100(address who, uint256 amount) public returns (uint256) {
+        require(amount > 100, "amount too small 100");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][100] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update101(address who, uint256 amount) public returns (uint256) {
+        require(amount > 101, "amount too small 101");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][101] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update102(address who, uint256 amount) public returns (uint256) {
+        require(amount > 102, "amount too small 102");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][102] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update103(address who, uint256 amount) public returns (uint256) {
+        require(amount > 103, "amount too small 103");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][103] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update104(address who, uint256 amount) public returns (uint256) {
+        require(amount > 104, "amount too small 104");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][104] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update105(address who, uint256 amount) public returns (uint256) {
+        require(amount > 105, "amount too small 105");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][105] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update106(address who, uint256 amount) public returns (uint256) {
+        require(amount > 106, "amount too small 106");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][106] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update107(address who, uint256 amount) public returns (uint256) {
+        require(amount > 107, "amount too small 107");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][107] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update108(address who, uint256 amount) public returns (uint256) {
+        require(amount > 108, "amount too small 108");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][108] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update109(address who, uint256 amount) public returns (uint256) {
+        require(amount > 109, "amount too small 109");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][109] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update110(address who, uint256 amount) public returns (uint256) {
+        require(amount > 110, "amount too small 110");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][110] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update111(address who, uint256 amount) public returns (uint256) {
+        require(amount > 111, "amount too small 111");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][111] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update112(address who, uint256 amount) public returns (uint256) {
+        require(amount > 112, "amount too small 112");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][112] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update113(address who, uint256 amount) public returns (uint256) {
+        require(amount > 113, "amount too small 113");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][113] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update114(address who, uint256 amount) public returns (uint256) {
+        require(amount > 114, "amount too small 114");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][114] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update115(address who, uint256 amount) public returns (uint256) {
+        require(amount > 115, "amount too small 115");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][115] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update116(address who, uint256 amount) public returns (uint256) {
+        require(amount > 116, "amount too small 116");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][116] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update117(address who, uint256 amount) public returns (uint256) {
+        require(amount > 117, "amount too small 117");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][117] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update118(address who, uint256 amount) public returns (uint256) {
+        require(amount > 118, "amount too small 118");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][118] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update119(address who, uint256 amount) public returns (uint256) {
+        require(amount > 119, "amount too small 119");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][119] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update120(address who, uint256 amount) public returns (uint256) {
+        require(amount > 120, "amount too small 120");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][120] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update121(address who, uint256 amount) public returns (uint256) {
+        require(amount > 121, "amount too small 121");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][121] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update122(address who, uint256 amount) public returns (uint256) {
+        require(amount > 122, "amount too small 122");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][122] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update123(address who, uint256 amount) public returns (uint256) {
+        require(amount > 123, "amount too small 123");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][123] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update124(address who, uint256 amount) public returns (uint256) {
+        require(amount > 124, "amount too small 124");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][124] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update125(address who, uint256 amount) public returns (uint256) {
+        require(amount > 125, "amount too small 125");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][125] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update126(address who, uint256 amount) public returns (uint256) {
+        require(amount > 126, "amount too small 126");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][126] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update127(address who, uint256 amount) public returns (uint256) {
+        require(amount > 127, "amount too small 127");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][127] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update128(address who, uint256 amount) public returns (uint256) {
+        require(amount > 128, "amount too small 128");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][128] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update129(address who, uint256 amount) public returns (uint256) {
+        require(amount > 129, "amount too small 129");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][129] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update130(address who, uint256 amount) public returns (uint256) {
+        require(amount > 130, "amount too small 130");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][130] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update131(address who, uint256 amount) public returns (uint256) {
+        require(amount > 131, "amount too small 131");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][131] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update132(address who, uint256 amount) public returns (uint256) {
+        require(amount > 132, "amount too small 132");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][132] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update133(address who, uint256 amount) public returns (uint256) {
+        require(amount > 133, "amount too small 133");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][133] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update134(address who, uint256 amount) public returns (uint256) {
+        require(amount > 134, "amount too small 134");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][134] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update135(address who, uint256 amount) public returns (uint256) {
+        require(amount > 135, "amount too small 135");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][135] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update136(address who, uint256 amount) public returns (uint256) {
+        require(amount > 136, "amount too small 136");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][136] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update137(address who, uint256 amount) public returns (uint256) {
+        require(amount > 137, "amount too small 137");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][137] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update138(address who, uint256 amount) public returns (uint256) {
+        require(amount > 138, "amount too small 138");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][138] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update139(address who, uint256 amount) public returns (uint256) {
+        require(amount > 139, "amount too small 139");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][139] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update140(address who, uint256 amount) public returns (uint256) {
+        require(amount > 140, "amount too small 140");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][140] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update141(address who, uint256 amount) public returns (uint256) {
+        require(amount > 141, "amount too small 141");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][141] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update142(address who, uint256 amount) public returns (uint256) {
+        require(amount > 142, "amount too small 142");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][142] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update143(address who, uint256 amount) public returns (uint256) {
+        require(amount > 143, "amount too small 143");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][143] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update144(address who, uint256 amount) public returns (uint256) {
+        require(amount > 144, "amount too small 144");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][144] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update145(address who, uint256 amount) public returns (uint256) {
+        require(amount > 145, "amount too small 145");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][145] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update146(address who, uint256 amount) public returns (uint256) {
+        require(amount > 146, "amount too small 146");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][146] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update147(address who, uint256 amount) public returns (uint256) {
+        require(amount > 147, "amount too small 147");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][147] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update148(address who, uint256 amount) public returns (uint256) {
+        require(amount > 148, "amount too small 148");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][148] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update149(address who, uint256 amount) public returns (uint256) {
+        require(amount > 149, "amount too small 149");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][149] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update150(address who, uint256 amount) public returns (uint256) {
+        require(amount > 150, "amount too small 150");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][150] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update151(address who, uint256 amount) public returns (uint256) {
+        require(amount > 151, "amount too small 151");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][151] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update152(address who, uint256 amount) public returns (uint256) {
+        require(amount > 152, "amount too small 152");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][152] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update153(address who, uint256 amount) public returns (uint256) {
+        require(amount > 153, "amount too small 153");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][153] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update154(address who, uint256 amount) public returns (uint256) {
+        require(amount > 154, "amount too small 154");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][154] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update155(address who, uint256 amount) public returns (uint256) {
+        require(amount > 155, "amount too small 155");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][155] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update156(address who, uint256 amount) public returns (uint256) {
+        require(amount > 156, "amount too small 156");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][156] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update157(address who, uint256 amount) public returns (uint256) {
+        require(amount > 157, "amount too small 157");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][157] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update158(address who, uint256 amount) public returns (uint256) {
+        require(amount > 158, "amount too small 158");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][158] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update159(address who, uint256 amount) public returns (uint256) {
+        require(amount > 159, "amount too small 159");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][159] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update160(address who, uint256 amount) public returns (uint256) {
+        require(amount > 160, "amount too small 160");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][160] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update161(address who, uint256 amount) public returns (uint256) {
+        require(amount > 161, "amount too small 161");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][161] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update162(address who, uint256 amount) public returns (uint256) {
+        require(amount > 162, "amount too small 162");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][162] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update163(address who, uint256 amount) public returns (uint256) {
+        require(amount > 163, "amount too small 163");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][163] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update164(address who, uint256 amount) public returns (uint256) {
+        require(amount > 164, "amount too small 164");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][164] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update165(address who, uint256 amount) public returns (uint256) {
+        require(amount > 165, "amount too small 165");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][165] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update166(address who, uint256 amount) public returns (uint256) {
+        require(amount > 166, "amount too small 166");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][166] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update167(address who, uint256 amount) public returns (uint256) {
+        require(amount > 167, "amount too small 167");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][167] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update168(address who, uint256 amount) public returns (uint256) {
+        require(amount > 168, "amount too small 168");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][168] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update169(address who, uint256 amount) public returns (uint256) {
+        require(amount > 169, "amount too small 169");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][169] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update170(address who, uint256 amount) public returns (uint256) {
+        require(amount > 170, "amount too small 170");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][170] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update171(address who, uint256 amount) public returns (uint256) {
+        require(amount > 171, "amount too small 171");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][171] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update172(address who, uint256 amount) public returns (uint256) {
+        require(amount > 172, "amount too small 172");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][172] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update173(address who, uint256 amount) public returns (uint256) {
+        require(amount > 173, "amount too small 173");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][173] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update174(address who, uint256 amount) public returns (uint256) {
+        require(amount > 174, "amount too small 174");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][174] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update175(address who, uint256 amount) public returns (uint256) {
+        require(amount > 175, "amount too small 175");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][175] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update176(address who, uint256 amount) public returns (uint256) {
+        require(amount > 176, "amount too small 176");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][176] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update177(address who, uint256 amount) public returns (uint256) {
+        require(amount > 177, "amount too small 177");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][177] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update178(address who, uint256 amount) public returns (uint256) {
+        require(amount > 178, "amount too small 178");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][178] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update179(address who, uint256 amount) public returns (uint256) {
+        require(amount > 179, "amount too small 179");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][179] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update180(address who, uint256 amount) public returns (uint256) {
+        require(amount > 180, "amount too small 180");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][180] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update181(address who, uint256 amount) public returns (uint256) {
+        require(amount > 181, "amount too small 181");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][181] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update182(address who, uint256 amount) public returns (uint256) {
+        require(amount > 182, "amount too small 182");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][182] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update183(address who, uint256 amount) public returns (uint256) {
+        require(amount > 183, "amount too small 183");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][183] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update184(address who, uint256 amount) public returns (uint256) {
+        require(amount > 184, "amount too small 184");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][184] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update185(address who, uint256 amount) public returns (uint256) {
+        require(amount > 185, "amount too small 185");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][185] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update186(address who, uint256 amount) public returns (uint256) {
+        require(amount > 186, "amount too small 186");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][186] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update187(address who, uint256 amount) public returns (uint256) {
+        require(amount > 187, "amount too small 187");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][187] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update188(address who, uint256 amount) public returns (uint256) {
+        require(amount > 188, "amount too small 188");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][188] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update189(address who, uint256 amount) public returns (uint256) {
+        require(amount > 189, "amount too small 189");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][189] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update190(address who, uint256 amount) public returns (uint256) {
+        require(amount > 190, "amount too small 190");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][190] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update191(address who, uint256 amount) public returns (uint256) {
+        require(amount > 191, "amount too small 191");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][191] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update192(address who, uint256 amount) public returns (uint256) {
+        require(amount > 192, "amount too small 192");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][192] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update193(address who, uint256 amount) public returns (uint256) {
+        require(amount > 193, "amount too small 193");
+        uint256 scaled = amount * 5 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][193] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update194(address who, uint256 amount) public returns (uint256) {
+        require(amount > 194, "amount too small 194");
+        uint256 scaled = amount * 6 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][194] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update195(address who, uint256 amount) public returns (uint256) {
+        require(amount > 195, "amount too small 195");
+        uint256 scaled = amount * 7 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][195] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update196(address who, uint256 amount) public returns (uint256) {
+        require(amount > 196, "amount too small 196");
+        uint256 scaled = amount * 1 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][196] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update197(address who, uint256 amount) public returns (uint256) {
+        require(amount > 197, "amount too small 197");
+        uint256 scaled = amount * 2 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][197] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update198(address who, uint256 amount) public returns (uint256) {
+        require(amount > 198, "amount too small 198");
+        uint256 scaled = amount * 3 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][198] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+
+    function update199(address who, uint256 amount) public returns (uint256) {
+        require(amount > 199, "amount too small 199");
+        uint256 scaled = amount * 4 + total;
+        balances[who] = balances[who] > scaled ? balances[who] - scaled : balances[who] + scaled;
+        approvals[who][199] = true;
+        total = total + amount;
+        emit Updated(who, amount);
+        return balances[who];
+    }
+}
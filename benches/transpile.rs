@@ -0,0 +1,36 @@
+//! Parse and transpile throughput on a small hand-written token and a
+//! large synthetic contract, both under `fixtures/`. Run with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sol2clarity::{parser, transpile_source, TranspileOptions};
+
+const SMALL: &str = include_str!("fixtures/small.sol");
+/// 200 functions mixing state and map updates, `require`s, locals,
+/// ternaries and events, so every stage sees a representative workload.
+const LARGE: &str = include_str!("fixtures/large.sol");
+
+fn benchmarks(c: &mut Criterion) {
+    let options = TranspileOptions::default();
+    let fixtures = [("small", SMALL), ("large", LARGE)];
+
+    let mut group = c.benchmark_group("parse_all");
+    for (name, source) in fixtures {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), source, |b, source| {
+            b.iter(|| parser::parse_all(source).expect("fixture parses"))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("transpile");
+    for (name, source) in fixtures {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), source, |b, source| {
+            b.iter(|| transpile_source(source, &options).expect("fixture transpiles"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::transpiler::ClarityVersion;
+
+/// Writes a generated contract into a Clarinet project rooted at `root`:
+/// `contracts/<name>.clar`, a `[contracts.<name>]` entry in `Clarinet.toml`
+/// and a `tests/<name>_test.ts` stub. The manifest entry deploys the contract
+/// at `version`, in the matching epoch. Re-running updates the contract source
+/// but never duplicates manifest entries or overwrites existing tests.
+pub fn write_contract(root: &Path, name: &str, clarity_code: &str, version: ClarityVersion) -> Result<PathBuf> {
+    let mut project = Project::open(root)?;
+    let contract_file = project.add_contract(name, clarity_code, version);
+    project.write()?;
+    Ok(contract_file)
+}
+
+/// Changes to a Clarinet project, held in memory so they can be reported,
+/// as `--dry-run` does, before anything is written.
+pub struct Project {
+    root: PathBuf,
+    manifest_path: PathBuf,
+    manifest: String,
+    manifest_changed: bool,
+    files: Vec<(PathBuf, String)>,
+}
+
+impl Project {
+    /// Reads the project's `Clarinet.toml`, or starts one with a `[project]`
+    /// table if there is none.
+    pub fn open(root: &Path) -> Result<Project> {
+        let manifest_path = root.join("Clarinet.toml");
+        let (manifest, manifest_changed) = if manifest_path.exists() {
+            let contents = fs::read_to_string(&manifest_path)
+                .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+            (contents, false)
+        } else {
+            (project_table(&manifest_path), true)
+        };
+        Ok(Project { root: root.to_path_buf(), manifest_path, manifest, manifest_changed, files: Vec::new() })
+    }
+
+    /// Adds a contract, its manifest entry and its test stub, and returns
+    /// the path of the contract file.
+    pub fn add_contract(&mut self, name: &str, clarity_code: &str, version: ClarityVersion) -> PathBuf {
+        let contract_file = self.root.join("contracts").join(format!("{}.clar", name));
+        self.files.retain(|(path, _)| *path != contract_file);
+        self.files.push((contract_file.clone(), clarity_code.to_string()));
+
+        self.register_contract(name, version);
+
+        let test_file = self.root.join("tests").join(format!("{}_test.ts", name));
+        if !test_file.exists() && !self.files.iter().any(|(path, _)| *path == test_file) {
+            self.files.push((test_file, test_stub(name)));
+        }
+        contract_file
+    }
+
+    /// Every file that writing would create or replace, with its contents.
+    pub fn pending(&self) -> Vec<(&Path, &str)> {
+        let manifest = self.manifest_changed.then_some((self.manifest_path.as_path(), self.manifest.as_str()));
+        self.files.iter()
+            .map(|(path, contents)| (path.as_path(), contents.as_str()))
+            .chain(manifest)
+            .collect()
+    }
+
+    /// Writes the pending files, creating their directories as needed.
+    pub fn write(&self) -> Result<()> {
+        for (path, contents) in self.pending() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            fs::write(path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Adds a `[contracts.<name>]` table to the manifest unless it is
+    /// already present.
+    fn register_contract(&mut self, name: &str, version: ClarityVersion) {
+        let header = format!("[contracts.{}]", name);
+        if self.manifest.lines().any(|line| line.trim() == header) {
+            return;
+        }
+
+        let contents = &mut self.manifest;
+        if !contents.is_empty() && !contents.ends_with("\n\n") {
+            contents.push_str(if contents.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        contents.push_str(&format!(
+            "{}\npath = \"contracts/{}.clar\"\nclarity_version = {}\nepoch = {}\n",
+            header, name, version.number(), version.epoch()
+        ));
+        self.manifest_changed = true;
+    }
+}
+
+fn project_table(manifest: &Path) -> String {
+    let project_name = manifest.parent()
+        .and_then(|dir| dir.canonicalize().ok())
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "sol2clarity-project".to_string());
+
+    format!(
+        "[project]\nname = \"{}\"\ndescription = \"\"\nauthors = []\ntelemetry = false\nrequirements = []\n",
+        project_name
+    )
+}
+
+fn test_stub(name: &str) -> String {
+    format!(
+r#"import {{ describe, expect, it }} from "vitest";
+
+const accounts = simnet.getAccounts();
+const deployer = accounts.get("deployer")!;
+
+describe("{name}", () => {{
+  it("is deployed", () => {{
+    const source = simnet.getContractSource("{name}");
+    expect(source).toBeDefined();
+    expect(deployer).toBeDefined();
+  }});
+}});
+"#,
+        name = name
+    )
+}
@@ -1,79 +1,232 @@
-use crate::transpiler::converter::{ClarityContract, ClarityExpression, ClarityFunction};
-use anyhow::Result;
+mod pretty;
 
-fn to_kebab_case(s: &str) -> String {
-    if s.chars().all(|c| c.is_uppercase() || c.is_numeric() || c == '_') {
-        return s.to_string();
-    }
+use crate::transpiler::converter::{default_value, ClarityContract, ClarityExpression, ClarityFunction};
+use crate::transpiler::{ClarityType, NamingStyle, TranspileOptions};
+use anyhow::{anyhow, Result};
+use pretty::pretty;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Converts a Solidity identifier (camelCase, PascalCase, snake_case or
+/// SCREAMING_CASE) into a Clarity kebab-case name.
+///
+/// Runs of capitals are kept together (`USDCToken` -> `usdc-token`) and a
+/// separator is inserted where a digit is followed by a letter
+/// (`token2Owner` -> `token2-owner`). Already kebab-cased input is returned
+/// unchanged.
+pub fn to_kebab_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
     let mut out = String::new();
-    for (i, c) in s.chars().enumerate() {
-        if c.is_uppercase() {
-            if i != 0 { out.push('-'); }
-            out.push(c.to_ascii_lowercase());
-        } else {
-            out.push(c);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !out.is_empty() && !out.ends_with('-') {
+                out.push('-');
+            }
+            continue;
         }
+
+        let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+        let next = chars.get(i + 1).copied();
+        let boundary = match prev {
+            Some(p) if c.is_uppercase() => {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            }
+            Some(p) if c.is_alphabetic() => p.is_ascii_digit(),
+            _ => false,
+        };
+
+        if boundary && !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        out.extend(c.to_lowercase());
+    }
+
+    out.trim_end_matches('-').to_string()
+}
+
+/// Renames a Solidity identifier according to the configured naming style.
+///
+/// SCREAMING_CASE names are left alone in every style, as they conventionally
+/// denote constants in both languages.
+pub fn apply_naming(name: &str, style: NamingStyle) -> String {
+    if !name.chars().any(|c| c.is_lowercase()) {
+        return name.to_string();
+    }
+    match style {
+        NamingStyle::Kebab => to_kebab_case(name),
+        NamingStyle::Preserve => name.to_string(),
+        NamingStyle::Snake => to_kebab_case(name).replace('-', "_"),
     }
-    out
 }
 
-pub fn generate(contract: ClarityContract) -> Result<String> {
+/// Names the read-only getter for a map, public variable or public
+/// constant. Constant names are lowercased for the kebab and snake styles
+/// (`LIMIT` -> `get-limit`).
+fn getter_name(name: &str, style: NamingStyle) -> String {
+    match style {
+        NamingStyle::Kebab => format!("get-{}", to_kebab_case(name)),
+        NamingStyle::Snake => format!("get_{}", to_kebab_case(name).replace('-', "_")),
+        NamingStyle::Preserve => {
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => format!("get{}{}", first.to_uppercase(), chars.as_str()),
+                None => "get".to_string(),
+            }
+        }
+    }
+}
+
+/// Names the trait generated for a Solidity interface, dropping the
+/// conventional `I` prefix (`IToken` -> `token-trait`).
+pub fn trait_name(interface: &str) -> String {
+    let mut chars = interface.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some('I'), Some(c)) if c.is_uppercase() => &interface[1..],
+        _ => interface,
+    };
+    format!("{}-trait", to_kebab_case(name))
+}
+
+pub fn generate(mut contract: ClarityContract, options: &TranspileOptions) -> Result<String> {
+    if options.sort_definitions {
+        // Clarity resolves definitions regardless of their order in the file
+        contract.data_vars.sort_by(|a, b| a.name.cmp(&b.name));
+        contract.maps.sort_by(|a, b| a.name.cmp(&b.name));
+        contract.events.sort_by(|a, b| a.name.cmp(&b.name));
+        contract.functions.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    if contract.is_interface {
+        return Ok(generate_trait(&contract, options));
+    }
+    check_getter_names(&contract, options)?;
+
     let mut output = String::new();
 
     output.push_str(&format!(
         ";; Contract: {}\n",
         contract.name
     ));
-    output.push_str(";; Auto-generated Clarity contract from Solidity source\n\n");
+    output.push_str(";; Auto-generated Clarity contract from Solidity source\n");
+    output.push_str(&generate_provenance(&contract, options));
+    end_section(&mut output);
 
-    for var in &contract.data_vars {
-        if var.is_constant {
+    if !contract.traits.is_empty() {
+        if options.emit_comments {
+            output.push_str(";; @desc Traits of the interfaces taken as parameters\n");
+        }
+        for trait_ref in &contract.traits {
             output.push_str(&format!(
-                ";; @desc Constant value for {}\n",
-                var.name
+                "(use-trait {} {}.{})\n",
+                trait_ref.alias, trait_ref.contract, trait_ref.alias
             ));
+        }
+        end_section(&mut output);
+    }
+
+    for var in &contract.data_vars {
+        if var.is_constant {
+            if options.emit_comments {
+                output.push_str(&format!(
+                    ";; @desc Constant value for {}\n",
+                    var.name
+                ));
+            }
             output.push_str(&format!(
                 "(define-constant {} {})\n",
                 var.name, var.initial_value
             ));
+
+            if var.visibility.as_deref() == Some("public") && !defines_function(&contract, &getter_name(&var.name, options.naming)) {
+                if options.emit_comments {
+                    output.push_str(&format!(
+                        ";; @desc Getter for public constant {}\n",
+                        var.name
+                    ));
+                }
+                output.push_str(&format!(
+                    "(define-read-only ({})\n{}{})\n",
+                    getter_name(&var.name, options.naming), " ".repeat(options.indent), getter_result(&var.name, options)
+                ));
+            }
         }
     }
-    output.push_str("\n");
+    end_section(&mut output);
 
     for map in &contract.maps {
-        output.push_str(&format!(
-            ";; @desc Map storing {} values\n",
-            map.name
-        ));
-        let map_name = to_kebab_case(&map.name);
+        if options.emit_comments {
+            output.push_str(&format!(
+                ";; @desc Map storing {} values\n",
+                map.name
+            ));
+        }
+        let map_name = &map.name;
         output.push_str(&format!(
             "(define-map {} {} {})\n",
             map_name, map.key_type, map.value_type
         ));
+        if defines_function(&contract, &getter_name(map_name, options.naming)) {
+            continue;
+        }
 
+        if options.emit_comments {
+            output.push_str(&format!(
+                ";; @desc Getter for map {}\n",
+                map.name
+            ));
+        }
+        // Flattened nested maps take one parameter per key field
+        let (params, key) = if let ClarityType::Tuple(fields) = &map.key_type {
+            (
+                fields.iter()
+                    .map(|(name, field_type)| format!("({} {})", name, field_type))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                format!("{{{}}}", fields.iter()
+                    .map(|(name, _)| format!("{}: {}", name, name))
+                    .collect::<Vec<_>>()
+                    .join(", ")),
+            )
+        } else {
+            (format!("(key {})", map.key_type), "key".to_string())
+        };
         output.push_str(&format!(
-            ";; @desc Getter for map {}\n",
-            map.name
-        ));
-        output.push_str(&format!(
-            "(define-read-only (get-{} (key {}))\n",
-            map_name, map.key_type
-        ));
-        output.push_str(&format!(
-            "  (ok (map-get? {} key)))\n\n",
-            map_name
+            "(define-read-only ({} {})\n",
+            getter_name(map_name, options.naming), params
         ));
+        if options.optional_map_getters {
+            output.push_str(&format!(
+                "{}{})\n\n",
+                " ".repeat(options.indent), getter_result(&format!("(map-get? {} {})", map_name, key), options)
+            ));
+        } else {
+            output.push_str(&format!(
+                "{}{})\n\n",
+                " ".repeat(options.indent),
+                getter_result(&format!("(default-to {} (map-get? {} {}))", default_value(&map.value_type), map_name, key), options)
+            ));
+        }
     }
 
     for var in &contract.data_vars {
         if !var.is_constant {
-            output.push_str(&format!(
-                ";; @desc Stores the {} value\n",
-                var.name
-            ));
-            if var.visibility.as_ref().map_or(false, |v| v == "public") {
-                output.push_str(";; @access public\n");
+            let is_public = var.visibility.as_deref() == Some("public");
+            if options.emit_comments {
+                output.push_str(&format!(
+                    ";; @desc Stores the {} value\n",
+                    var.name
+                ));
+                if is_public {
+                    output.push_str(";; @access public\n");
+                }
+                if let Some((enum_name, members)) = &var.enum_members {
+                    let values: Vec<String> = members.iter()
+                        .enumerate()
+                        .map(|(i, member)| format!("u{} = {}", i, member))
+                        .collect();
+                    output.push_str(&format!(";; @enum {}: {}\n", enum_name, values.join(", ")));
+                }
             }
             let var_name = var.name.clone();
             output.push_str(&format!(
@@ -81,57 +234,210 @@ pub fn generate(contract: ClarityContract) -> Result<String> {
                 var_name, var.var_type, var.initial_value
             ));
 
-            if var.visibility.as_ref().map_or(false, |v| v == "public") {
-                output.push_str(&format!(
-                    ";; @desc Getter for public variable {}\n",
-                    var.name
-                ));
+            if is_public && !defines_function(&contract, &getter_name(&var_name, options.naming)) {
+                if options.emit_comments {
+                    output.push_str(&format!(
+                        ";; @desc Getter for public variable {}\n",
+                        var.name
+                    ));
+                }
                 output.push_str(&format!(
-                    "(define-read-only (get-{})\n",
-                    var_name
+                    "(define-read-only ({})\n",
+                    getter_name(&var_name, options.naming)
                 ));
                 output.push_str(&format!(
-                    "  (ok (var-get {})))\n\n",
-                    var_name
+                    "{}{})\n\n",
+                    " ".repeat(options.indent), getter_result(&format!("(var-get {})", var_name), options)
                 ));
             }
         }
     }
-    output.push_str("\n");
+    end_section(&mut output);
 
-    for event in &contract.events {
-        output.push_str(&format!(
-            ";; @desc Event: {}\n",
-            event.name
-        ));
-        output.push_str(";; @fields ");
-        for field in &event.fields {
-            output.push_str(&format!("{}{}: {}, ", 
-                if field.indexed { "(indexed) " } else { "" },
-                field.name, 
-                field.field_type));
+    if options.emit_comments {
+        for event in &contract.events {
+            output.push_str(&format!(
+                ";; @desc Event: {}\n",
+                event.name
+            ));
+            output.push_str(";; @fields ");
+            for field in &event.fields {
+                output.push_str(&format!("{}{}: {}, ", 
+                    if field.indexed { "(indexed) " } else { "" },
+                    field.name, 
+                    field.field_type));
+            }
+            output.push_str("\n\n");
         }
-        output.push_str("\n\n");
     }
 
+    if options.group_by_visibility {
+        // Stable, so source (or sorted) order is kept within each group
+        contract.functions.sort_by_key(visibility_group);
+    }
+    let mut group = None;
     for func in &contract.functions {
-        output.push_str(&generate_function(func));
-        output.push_str("\n");
+        if options.group_by_visibility && group != Some(visibility_group(func)) {
+            group = Some(visibility_group(func));
+            output.push_str(&format!(";; ---- {} ----\n\n", VISIBILITY_GROUPS[visibility_group(func)]));
+        }
+        output.push_str(&generate_function(func, options));
+        output.push('\n');
     }
 
     Ok(output)
 }
 
-fn generate_function(func: &ClarityFunction) -> String {
+/// Section banners for `group_by_visibility`, in output order.
+const VISIBILITY_GROUPS: [&str; 3] = ["Public Functions", "Read-Only Functions", "Private Functions"];
+
+fn visibility_group(func: &ClarityFunction) -> usize {
+    match (func.public, func.read_only) {
+        (true, false) => 0,
+        (true, true) => 1,
+        _ => 2,
+    }
+}
+
+/// Fails if two generated getters would share a name, as with a variable
+/// `totalSupply` and a map `total_supply`. A function that already has a
+/// getter's name, such as Solidity's own `getCount()`, replaces the getter.
+fn check_getter_names(contract: &ClarityContract, options: &TranspileOptions) -> Result<()> {
+    let mut getters: Vec<(String, String)> = Vec::new();
+    let public_vars = contract.data_vars.iter()
+        .filter(|var| var.visibility.as_deref() == Some("public"))
+        .map(|var| (&var.name, if var.is_constant { "constant" } else { "variable" }));
+    let maps = contract.maps.iter().map(|map| (&map.name, "map"));
+    for (name, kind) in public_vars.chain(maps) {
+        let getter = getter_name(name, options.naming);
+        let owner = format!("{} {}", kind, name);
+        if let Some((_, other)) = getters.iter().find(|(existing, _)| *existing == getter) {
+            return Err(anyhow!("Getter {} for {} collides with the getter for {}", getter, owner, other));
+        }
+        getters.push((getter, owner));
+    }
+    Ok(())
+}
+
+/// What a generated getter returns for `value`: wrapped in `ok` like other
+/// read-only functions, or raw when `getters_return_response` is off.
+fn getter_result(value: &str, options: &TranspileOptions) -> String {
+    if options.getters_return_response {
+        format!("(ok {})", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Ends a section of the output with a blank line, unless it already ends
+/// with one, as after a getter or an empty section.
+fn end_section(output: &mut String) {
+    if !output.ends_with("\n\n") {
+        output.push('\n');
+    }
+}
+
+fn defines_function(contract: &ClarityContract, name: &str) -> bool {
+    contract.functions.iter().any(|func| func.name == name)
+}
+
+/// Renders error codes as a JSON object from code to message, for
+/// frontends that need to explain a failed call.
+pub fn generate_manifest(error_codes: &[(u32, String)]) -> String {
+    let entries: Vec<String> = error_codes.iter()
+        .map(|(code, message)| format!("  \"{}\": {}", code, json_string(message)))
+        .collect();
+    if entries.is_empty() {
+        return "{}\n".to_string();
+    }
+    format!("{{\n{}\n}}\n", entries.join(",\n"))
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Header lines recording where the contract came from: the source file,
+/// its Solidity pragma, the transpiler version and, if enabled, the time.
+fn generate_provenance(contract: &ClarityContract, options: &TranspileOptions) -> String {
     let mut output = String::new();
+    if let Some(source_name) = &options.source_name {
+        output.push_str(&format!(";; Source: {}\n", source_name));
+    }
+    if let Some(pragma) = &contract.pragma {
+        output.push_str(&format!(";; Solidity: {}\n", pragma));
+    }
+    output.push_str(&format!(";; Generated by sol2clarity {}\n", env!("CARGO_PKG_VERSION")));
+    if options.header_timestamp {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        output.push_str(&format!(";; Generated at: {} (unix time)\n", seconds));
+    }
+    output
+}
+
+fn generate_trait(contract: &ClarityContract, options: &TranspileOptions) -> String {
+    let mut output = String::new();
+
+    if options.emit_comments {
+        output.push_str(&format!(";; Trait: {}\n", contract.name));
+        output.push_str(";; Auto-generated Clarity trait from Solidity interface\n\n");
+    }
+
+    output.push_str(&format!("(define-trait {} (", trait_name(&contract.name)));
+    let signatures: Vec<String> = contract.functions.iter()
+        .map(|func| format!(
+            "({} ({}) (response {} uint))",
+            func.name,
+            func.params.iter()
+                .map(|p| p.param_type.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            func.return_type.as_deref().unwrap_or("bool")
+        ))
+        .collect();
+    output.push_str(&signatures.join(&format!("\n{}", " ".repeat(options.indent))));
+    output.push_str("))\n");
+    output
+}
+
+fn generate_function(func: &ClarityFunction, options: &TranspileOptions) -> String {
+    let mut output = String::new();
+
+    for note in &func.notes {
+        output.push_str(&format!(";; {}\n", note));
+    }
+
+    if options.emit_comments {
+        output.push_str(&format!(";; Function: {}\n", func.name));
 
-    output.push_str(&format!(";; Function: {}\n", func.name));
+        if func.read_only {
+            output.push_str(";; @access read-only\n");
+        }
 
-    if func.read_only {
-        output.push_str(";; @access read-only\n");
+        if func.wraps_response {
+            output.push_str(&format!(
+                ";; @returns (response {} uint)\n",
+                func.return_type.as_deref().unwrap_or("bool")
+            ));
+        }
     }
 
-    if func.public {
+    if func.public && func.read_only {
+        output.push_str(&format!("(define-read-only ({}", func.name));
+    } else if func.public {
         output.push_str(&format!("(define-public ({}", func.name));
     } else {
         output.push_str(&format!("(define-private ({}", func.name));
@@ -142,64 +448,46 @@ fn generate_function(func: &ClarityFunction) -> String {
     }
     output.push_str(")\n");
 
-    output.push_str("  ");
-    if func.body.len() > 1 {
-        output.push_str("(begin\n    ");
-        for expr in &func.body[..func.body.len() - 1] {
-            output.push_str(&generate_expression(expr));
-            output.push_str("\n    ");
-        }
-        output.push_str("(ok ");
-        output.push_str(&generate_expression(func.body.last().unwrap()));
-        output.push_str("))");
-    } else if let Some(last_expr) = func.body.last() {
-        output.push_str("(ok ");
-        output.push_str(&generate_expression(last_expr));
-        output.push_str(")");
+    let call = |name: &str, args: Vec<ClarityExpression>| ClarityExpression::FunctionCall(name.to_string(), args);
+    let ok_true = || call("ok", vec![ClarityExpression::Literal("true".to_string())]);
+    let body = if !func.wraps_response {
+        match func.body.as_slice() {
+            [] => ClarityExpression::Literal("true".to_string()),
+            [expr] => expr.clone(),
+            body => call("begin", body.to_vec()),
+        }
+    } else if func.return_type.is_none() || func.body.last().is_some_and(is_side_effect) {
+        // No value to return, so every statement runs for its side effect
+        match func.body.as_slice() {
+            [] => ok_true(),
+            body => call("begin", body.iter().cloned().chain(std::iter::once(ok_true())).collect()),
+        }
     } else {
-        output.push_str("(ok true)");
-    }
+        match func.body.as_slice() {
+            [] => ok_true(),
+            [expr] => call("ok", vec![expr.clone()]),
+            [statements @ .., last] => call(
+                "begin",
+                statements.iter().cloned().chain(std::iter::once(call("ok", vec![last.clone()]))).collect(),
+            ),
+        }
+    };
+    output.push_str(&" ".repeat(options.indent));
+    output.push_str(&pretty(&body, options.indent, options.indent));
 
     output.push_str(")\n");
     output
 }
 
-fn generate_expression(expr: &ClarityExpression) -> String {
+/// Whether `expr` is evaluated for its effect on contract state, so its
+/// result is not a meaningful return value.
+fn is_side_effect(expr: &ClarityExpression) -> bool {
     match expr {
-        ClarityExpression::Literal(val) => val.clone(),
-        ClarityExpression::Var(name) => name.clone(),
-        ClarityExpression::FunctionCall(name, args) => {
-            let mut output = format!("({}", name);
-            for arg in args {
-                output.push_str(" ");
-                output.push_str(&generate_expression(arg));
-            }
-            output.push(')');
-            output
-        }
-        ClarityExpression::MapGet(map_name, keys) => {
-            format!("(map-get? {} {})", map_name, keys.iter()
-                .map(|k| generate_expression(k))
-                .collect::<Vec<_>>()
-                .join(" "))
-        }
-        ClarityExpression::MapSet(map_name, keys, value) => {
-            format!("(map-set {} {} {})",
-                map_name,
-                keys.iter()
-                    .map(|k| generate_expression(k))
-                    .collect::<Vec<_>>()
-                    .join(" "),
-                generate_expression(value))
-        }
-        ClarityExpression::Print(args) => {
-            let mut output = String::from("(print");
-            for arg in args {
-                output.push_str(" ");
-                output.push_str(&generate_expression(arg));
-            }
-            output.push(')');
-            output
+        ClarityExpression::MapSet(..) | ClarityExpression::Print(_) => true,
+        ClarityExpression::Let(_, body) => body.last().is_some_and(is_side_effect),
+        ClarityExpression::FunctionCall(name, _) => {
+            matches!(name.as_str(), "var-set" | "map-set" | "map-insert" | "map-delete")
         }
+        _ => false,
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,127 @@
+use crate::transpiler::converter::ClarityExpression;
+
+/// Column past which an expression is broken over several lines.
+const MAX_WIDTH: usize = 100;
+
+/// The shape of a Clarity expression as it is printed.
+enum Sexp {
+    Atom(String),
+    List(Vec<Sexp>),
+    Tuple(Vec<(String, Sexp)>),
+}
+
+impl Sexp {
+    fn from_expression(expr: &ClarityExpression) -> Sexp {
+        let call = |name: &str, args: Vec<Sexp>| {
+            Sexp::List(std::iter::once(Sexp::Atom(name.to_string())).chain(args).collect())
+        };
+        match expr {
+            ClarityExpression::Literal(val) => Sexp::Atom(val.clone()),
+            ClarityExpression::Var(name) => Sexp::Atom(name.clone()),
+            ClarityExpression::FunctionCall(name, args) => {
+                call(name, args.iter().map(Sexp::from_expression).collect())
+            }
+            ClarityExpression::MapGet(map_name, keys) => call(
+                "map-get?",
+                std::iter::once(Sexp::Atom(map_name.clone()))
+                    .chain(keys.iter().map(Sexp::from_expression))
+                    .collect(),
+            ),
+            ClarityExpression::MapSet(map_name, keys, value) => call(
+                "map-set",
+                std::iter::once(Sexp::Atom(map_name.clone()))
+                    .chain(keys.iter().map(Sexp::from_expression))
+                    .chain(std::iter::once(Sexp::from_expression(value)))
+                    .collect(),
+            ),
+            ClarityExpression::Print(args) => call("print", args.iter().map(Sexp::from_expression).collect()),
+            ClarityExpression::Tuple(fields) => Sexp::Tuple(fields.iter()
+                .map(|(name, value)| (name.clone(), Sexp::from_expression(value)))
+                .collect()),
+            ClarityExpression::Let(bindings, body) => {
+                let bindings = bindings.iter()
+                    .map(|(name, value)| Sexp::List(vec![Sexp::Atom(name.clone()), Sexp::from_expression(value)]))
+                    .collect();
+                call(
+                    "let",
+                    std::iter::once(Sexp::List(bindings))
+                        .chain(body.iter().map(Sexp::from_expression))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn flat(&self) -> String {
+        match self {
+            Sexp::Atom(atom) => atom.clone(),
+            Sexp::List(items) => format!("({})", items.iter().map(Sexp::flat).collect::<Vec<_>>().join(" ")),
+            Sexp::Tuple(fields) => format!("{{{}}}", fields.iter()
+                .map(|(name, value)| format!("{}: {}", name, value.flat()))
+                .collect::<Vec<_>>()
+                .join(", ")),
+        }
+    }
+
+    /// `begin` lists one statement per line even when they would fit on one.
+    fn always_breaks(&self) -> bool {
+        match self {
+            Sexp::List(items) => matches!(items.as_slice(), [Sexp::Atom(head), _, _, ..] if head == "begin"),
+            _ => false,
+        }
+    }
+
+    /// Renders the expression starting at `column`. Anything too wide
+    /// keeps its head and first argument on the opening line and puts each
+    /// remaining argument on its own line, `indent` columns further in.
+    fn layout(&self, column: usize, indent: usize) -> String {
+        let flat = self.flat();
+        if column + flat.len() <= MAX_WIDTH && !self.always_breaks() {
+            return flat;
+        }
+        match self {
+            Sexp::Atom(_) => flat,
+            Sexp::List(items) => match items.as_slice() {
+                [] => flat,
+                [Sexp::Atom(head), rest @ ..] if !rest.is_empty() => {
+                    let mut output = format!("({}", head);
+                    let (first, rest) = if head == "begin" {
+                        (None, rest)
+                    } else {
+                        (Some(&rest[0]), &rest[1..])
+                    };
+                    if let Some(first) = first {
+                        output.push(' ');
+                        output.push_str(&first.layout(column + head.len() + 2, indent));
+                    }
+                    let inner = column + indent;
+                    for item in rest {
+                        output.push('\n');
+                        output.push_str(&" ".repeat(inner));
+                        output.push_str(&item.layout(inner, indent));
+                    }
+                    output.push(')');
+                    output
+                }
+                // Lists without a head, such as `let` bindings, line up
+                // under their first item
+                items => {
+                    let lines: Vec<String> = items.iter().map(|item| item.layout(column + 1, indent)).collect();
+                    format!("({})", lines.join(&format!("\n{}", " ".repeat(column + 1))))
+                }
+            },
+            Sexp::Tuple(fields) => {
+                let lines: Vec<String> = fields.iter()
+                    .map(|(name, value)| format!("{}: {}", name, value.layout(column + name.len() + 3, indent)))
+                    .collect();
+                format!("{{{}}}", lines.join(&format!(",\n{}", " ".repeat(column + 1))))
+            }
+        }
+    }
+}
+
+/// Formats `expr` as it would appear at `column`, breaking it over lines
+/// indented `indent` spaces per level where it does not fit.
+pub(crate) fn pretty(expr: &ClarityExpression, column: usize, indent: usize) -> String {
+    Sexp::from_expression(expr).layout(column, indent)
+}
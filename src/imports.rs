@@ -0,0 +1,88 @@
+//! Resolution of relative `import` directives against the filesystem.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parser;
+use crate::transpiler::ast::{Contract, SourceUnit};
+use crate::transpiler::{Severity, Warning};
+
+/// Loads the contracts reachable through the relative imports of `unit`,
+/// the parsed contents of the file at `path`.
+///
+/// Each file is parsed once even when reached through several import paths,
+/// and contracts are deduplicated by name. Import cycles are reported as
+/// errors. Non-relative imports (packages such as `@openzeppelin/...`) are
+/// skipped, and returned as warnings alongside the contracts.
+pub fn load_imports(unit: &SourceUnit, path: &Path) -> Result<(Vec<Contract>, Vec<Warning>)> {
+    let path = fs::canonicalize(path)
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut loader = ImportLoader {
+        stack: vec![path],
+        ..Default::default()
+    };
+    loader.load_all(&unit.imports, &base_dir)?;
+    Ok((loader.contracts, loader.warnings))
+}
+
+#[derive(Default)]
+struct ImportLoader {
+    /// Files currently being loaded, outermost first
+    stack: Vec<PathBuf>,
+    loaded: Vec<PathBuf>,
+    contracts: Vec<Contract>,
+    warnings: Vec<Warning>,
+}
+
+impl ImportLoader {
+    fn load_all(&mut self, imports: &[String], base_dir: &Path) -> Result<()> {
+        for import in imports {
+            if !import.starts_with('.') {
+                self.warnings.push(Warning {
+                    severity: Severity::Warning,
+                    message: format!("import \"{}\" is not a relative path and is not resolved", import),
+                    line: None,
+                });
+                continue;
+            }
+            let path = base_dir.join(import);
+            let path = fs::canonicalize(&path)
+                .with_context(|| format!("Failed to resolve import \"{}\"", import))?;
+            self.load(path)?;
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, path: PathBuf) -> Result<()> {
+        if let Some(start) = self.stack.iter().position(|p| p == &path) {
+            let cycle: Vec<String> = self.stack[start..].iter()
+                .chain(std::iter::once(&path))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(anyhow!("Import cycle: {}", cycle.join(" -> ")));
+        }
+        if self.loaded.contains(&path) {
+            return Ok(());
+        }
+
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read import {}", path.display()))?;
+        let unit = parser::parse_source(&source)
+            .with_context(|| format!("Failed to parse import {}", path.display()))?;
+
+        self.stack.push(path.clone());
+        let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        self.load_all(&unit.imports, &base_dir)?;
+        self.stack.pop();
+
+        for contract in unit.contracts {
+            if !self.contracts.iter().any(|c| c.name == contract.name) {
+                self.contracts.push(contract);
+            }
+        }
+        self.loaded.push(path);
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+//! Solidity to Clarity transpiler.
+//!
+//! The pipeline is exposed both as individual stages (`parser`, `transpiler`,
+//! `generator`) and through [`transpile_source`], which runs all of them.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+
+pub mod clarinet;
+pub mod generator;
+pub mod imports;
+pub mod parser;
+pub mod transpiler;
+
+pub use transpiler::{
+    normalize_type, ClarityType, ClarityVersion, NamingStyle, ReservedNamePolicy, Severity, TranspileOptions, Warning,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A contract converted to Clarity.
+#[derive(Debug)]
+pub struct TranspiledContract {
+    pub name: String,
+    pub code: String,
+    /// Error codes used by the contract and the message or error name each
+    /// stands for, in allocation order.
+    pub error_codes: Vec<(u32, String)>,
+    /// Skipped constructs and lossy conversions found while converting it.
+    pub warnings: Vec<Warning>,
+}
+
+/// Transpiles every contract in a Solidity source, in declaration order.
+pub fn transpile_source(src: &str, opts: &TranspileOptions) -> Result<Vec<TranspiledContract>> {
+    let unit = parser::parse_source(src)
+        .with_context(|| "Failed to parse Solidity code")?;
+    let warnings = unit.imports.iter()
+        .map(|import| Warning {
+            severity: Severity::Warning,
+            message: format!("import \"{}\" is not resolved; only contracts in this source are transpiled", import),
+            line: None,
+        })
+        .collect();
+    transpile_unit(unit, Vec::new(), warnings, opts)
+}
+
+/// Transpiles every contract in the Solidity file at `path`.
+///
+/// Relative imports are resolved against the file's directory so that its
+/// contracts can inherit from imported ones; the imported contracts
+/// themselves are not transpiled. Errors name the file they came from.
+pub fn transpile_file(path: &Path, opts: &TranspileOptions) -> Result<Vec<TranspiledContract>> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read input file: {}", path.display()))?;
+    let transpile = || {
+        let unit = parser::parse_source(&source)
+            .with_context(|| "Failed to parse Solidity code")?;
+        let (imported, warnings) = imports::load_imports(&unit, path)?;
+        transpile_unit(unit, imported, warnings, opts)
+    };
+    transpile().with_context(|| format!("Failed to transpile {}", path.display()))
+}
+
+/// `source_warnings` are about the source as a whole, such as unresolved imports,
+/// and are reported with every contract in it.
+fn transpile_unit(
+    unit: transpiler::ast::SourceUnit,
+    imported: Vec<transpiler::ast::Contract>,
+    source_warnings: Vec<Warning>,
+    opts: &TranspileOptions,
+) -> Result<Vec<TranspiledContract>> {
+    let pragma = unit.pragma;
+    let own: Vec<String> = unit.contracts.iter().map(|c| c.name.clone()).collect();
+    let mut all = unit.contracts;
+    all.extend(imported.into_iter().filter(|c| !own.contains(&c.name)));
+    // Imported libraries are kept to be inlined into the contracts using them
+    let contracts = transpiler::resolve_inheritance(all)
+        .into_iter()
+        .filter(|c| own.contains(&c.name) || c.is_library)
+        .collect();
+
+    let mut outputs = Vec::new();
+    // Converted together so that contracts can call each other
+    for mut clarity_ast in transpiler::convert(contracts, opts)? {
+        let contract_name = clarity_ast.name.clone();
+        clarity_ast.pragma = pragma.clone();
+
+        let error_codes = clarity_ast.error_codes.clone();
+        let mut warnings = source_warnings.clone();
+        warnings.extend(clarity_ast.warnings.iter().cloned());
+
+        // Generate Clarity code
+        let clarity_code = generator::generate(clarity_ast, opts)
+            .with_context(|| format!("Failed to generate Clarity code for {}", contract_name))?;
+
+        outputs.push(TranspiledContract {
+            name: contract_name,
+            code: clarity_code,
+            error_codes,
+            warnings,
+        });
+    }
+
+    Ok(outputs)
+}
+
+/// Example contract transpiled by [`selftest`].
+const SELFTEST_SOURCE: &str = include_str!("../tests/fixtures/counter.sol");
+
+/// Transpiles a bundled example contract with the default options and
+/// returns the generated Clarity, as a quick check of an installed binary.
+/// Nothing is read from or written to the filesystem.
+pub fn selftest() -> Result<String> {
+    let outputs = transpile_source(SELFTEST_SOURCE, &TranspileOptions::default())
+        .with_context(|| "Self-test failed to transpile the bundled example")?;
+    let code = combine_contracts("selftest", outputs)?.code;
+    if !code.contains("(define-public ") {
+        return Err(anyhow!("Self-test produced no public functions"));
+    }
+    Ok(code)
+}
+
+/// Renders the warnings of every contract as a JSON array, one object per
+/// warning with the contract it belongs to.
+pub fn warnings_json(outputs: &[TranspiledContract]) -> String {
+    let entries: Vec<String> = outputs.iter()
+        .flat_map(|output| output.warnings.iter().map(move |warning| format!(
+            "  {{\"contract\": {}, \"severity\": \"{}\", \"line\": {}, \"message\": {}}}",
+            generator::json_string(&output.name),
+            warning.severity.as_str(),
+            warning.line.map_or("null".to_string(), |line| line.to_string()),
+            generator::json_string(&warning.message),
+        )))
+        .collect();
+    if entries.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+/// Concatenates transpiled contracts into a single Clarity contract named
+/// `name`, separated by comments naming each one. An error code one contract
+/// already uses for another meaning is renumbered in the next, so every code
+/// in the result identifies one error.
+///
+/// Fails if two contracts define the same top-level name, since Clarity has
+/// one namespace per contract, or if one calls another, since the callee is
+/// no longer deployed on its own.
+pub fn combine_contracts(name: &str, outputs: Vec<TranspiledContract>) -> Result<TranspiledContract> {
+    for output in &outputs {
+        let callee = outputs.iter().find(|other| {
+            let principal = format!(".{}", other.name.to_lowercase());
+            [" ", ")", "."].iter().any(|end| output.code.contains(&format!("{}{}", principal, end)))
+        });
+        if let Some(callee) = callee {
+            return Err(anyhow!(
+                "Cannot combine contracts into one file, {} calls {}; write them as separate contracts",
+                output.name, callee.name
+            ));
+        }
+    }
+
+    let mut defined: Vec<(&str, &str)> = Vec::new();
+    let mut collisions = Vec::new();
+    for output in &outputs {
+        for name in output.code.lines().filter_map(defined_name) {
+            match defined.iter().find(|(other, _)| *other == name) {
+                Some((_, first)) => collisions.push(format!("{} (in {} and {})", name, first, output.name)),
+                None => defined.push((name, &output.name)),
+            }
+        }
+    }
+    if !collisions.is_empty() {
+        return Err(anyhow!(
+            "Cannot combine contracts into one file, names are defined twice: {}",
+            collisions.join(", ")
+        ));
+    }
+
+    let mut error_codes: Vec<(u32, String)> = Vec::new();
+    let mut sections = Vec::new();
+    let mut warnings = Vec::new();
+    for output in outputs {
+        let mut next_code = error_codes.iter().chain(&output.error_codes)
+            .map(|(code, _)| *code + 1)
+            .max()
+            .unwrap_or(0);
+        let mut renumbered = Vec::new();
+        for (code, message) in output.error_codes {
+            match error_codes.iter().find(|(taken, _)| *taken == code) {
+                Some((_, meaning)) if *meaning == message => {}
+                Some(_) => {
+                    renumbered.push((code, next_code));
+                    error_codes.push((next_code, message));
+                    next_code += 1;
+                }
+                None => error_codes.push((code, message)),
+            }
+        }
+        let code: Vec<String> = output.code.split('\n')
+            .map(|line| renumber_error_constant(line, &renumbered))
+            .collect();
+        sections.push(format!(";; ---- {} ----\n{}", output.name, code.join("\n")));
+        warnings.extend(output.warnings);
+    }
+
+    Ok(TranspiledContract {
+        name: name.to_string(),
+        code: sections.join("\n"),
+        error_codes,
+        warnings,
+    })
+}
+
+/// `line` with the code of an `ERR_` constant definition replaced, if it is
+/// one of the `(old, new)` pairs in `renumbered`.
+fn renumber_error_constant(line: &str, renumbered: &[(u32, u32)]) -> String {
+    if !line.starts_with("(define-constant ") || !defined_name(line).is_some_and(|name| name.starts_with("ERR_")) {
+        return line.to_string();
+    }
+    for (old, new) in renumbered {
+        for (from, to) in [(format!(" u{})", old), format!(" u{})", new)), (format!(" (err u{}))", old), format!(" (err u{}))", new))] {
+            if let Some(head) = line.strip_suffix(from.as_str()) {
+                return format!("{}{}", head, to);
+            }
+        }
+    }
+    line.to_string()
+}
+
+/// The name introduced by a top-level `(define-... name` or
+/// `(define-... (name ...)` line.
+fn defined_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("(define-")?;
+    let (_, rest) = rest.split_once(' ')?;
+    let rest = rest.trim_start_matches('(');
+    rest.split(|c: char| c.is_whitespace() || c == ')').next()
+}
@@ -1,14 +1,8 @@
 use clap::{App, Arg};
 use std::fs;
 use std::path::Path;
-use anyhow::{Context, Result};
-
-mod parser;
-mod transpiler;
-mod generator;
-
-#[cfg(test)]
-mod tests;
+use anyhow::{anyhow, Context, Result};
+use sol2clarity::{clarinet, generator, transpiler, TranspiledContract};
 
 fn main() -> Result<()> {
     let matches = App::new("sol2clarity")
@@ -18,7 +12,7 @@ fn main() -> Result<()> {
         .arg(
             Arg::with_name("INPUT")
                 .help("Input Solidity file")
-                .required(true)
+                .required_unless("selftest")
                 .index(1),
         )
         .arg(
@@ -29,44 +23,331 @@ fn main() -> Result<()> {
                 .help("Output directory for Clarity files")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("no-comments")
+                .long("no-comments")
+                .help("Omit documentation comments from the generated Clarity"),
+        )
+        .arg(
+            Arg::with_name("utf8")
+                .long("utf8")
+                .help("Convert Solidity strings to string-utf8 instead of string-ascii"),
+        )
+        .arg(
+            Arg::with_name("max-string-length")
+                .long("max-string-length")
+                .value_name("LEN")
+                .help("Maximum length of converted string types (default: 256)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("guard-init")
+                .long("guard-init")
+                .help("Prevent the generated init function from running more than once"),
+        )
+        .arg(
+            Arg::with_name("max-iterations")
+                .long("max-iterations")
+                .value_name("N")
+                .help("Iteration bound for while loops lowered to fold (default: 32)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-list-length")
+                .long("max-list-length")
+                .value_name("LEN")
+                .help("Maximum length of lists converted from dynamic arrays (default: 256)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("indent")
+                .long("indent")
+                .value_name("N")
+                .help("Spaces per nesting level in the generated code (default: 2)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("clarinet")
+                .long("clarinet")
+                .help("Write output into a Clarinet project layout in the output directory"),
+        )
+        .arg(
+            Arg::with_name("reserved-names")
+                .long("reserved-names")
+                .value_name("POLICY")
+                .help("Rename identifiers that are reserved in Clarity, or reject them (default: rename)")
+                .possible_values(&["rename", "error"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("optional-getters")
+                .long("optional-getters")
+                .help("Return optionals from map getters instead of defaulting missing entries"),
+        )
+        .arg(
+            Arg::with_name("preserve-map-optionals")
+                .long("preserve-map-optionals")
+                .help("Return optionals from functions returning a mapping entry instead of defaulting it"),
+        )
+        .arg(
+            Arg::with_name("membership-checks")
+                .long("membership-checks")
+                .help("Compile m[k] != 0 and m[k] == 0 to is-some/is-none on the map entry"),
+        )
+        .arg(
+            Arg::with_name("raw-getters")
+                .long("raw-getters")
+                .help("Return raw values from generated getters instead of (ok ...) responses"),
+        )
+        .arg(
+            Arg::with_name("event-envelope")
+                .long("event-envelope")
+                .help("Print events as {topic: \"event\", name, data} tuples for indexers"),
+        )
+        .arg(
+            Arg::with_name("clarity-version")
+                .long("clarity-version")
+                .value_name("VERSION")
+                .help("Clarity version the output targets (default: 2)")
+                .possible_values(&["1", "2", "3"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("naming")
+                .long("naming")
+                .value_name("STYLE")
+                .help("Naming style for generated identifiers (default: kebab)")
+                .possible_values(&["kebab", "preserve", "snake"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("timestamp")
+                .long("timestamp")
+                .help("Record the generation time in the header of each output file"),
+        )
+        .arg(
+            Arg::with_name("strict")
+                .long("strict")
+                .help("Fail on unsupported constructs and globals with no Clarity equivalent instead of skipping them"),
+        )
+        .arg(
+            Arg::with_name("validate-inputs")
+                .long("validate-inputs")
+                .help("Reject zero for amount/value parameters of public functions"),
+        )
+        .arg(
+            Arg::with_name("single-file")
+                .long("single-file")
+                .value_name("NAME")
+                .help("Write all contracts into one file NAME.clar instead of one file each")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("optimize")
+                .long("optimize")
+                .help("Read map entries used more than once in a function only once"),
+        )
+        .arg(
+            Arg::with_name("group-by-visibility")
+                .long("group-by-visibility")
+                .help("Group functions into public, read-only and private sections"),
+        )
+        .arg(
+            Arg::with_name("sort")
+                .long("sort")
+                .help("Sort definitions alphabetically within each section of the output"),
+        )
+        .arg(
+            Arg::with_name("emit-manifest")
+                .long("emit-manifest")
+                .value_name("PATH")
+                .help("Write a JSON file mapping error codes to their messages")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("warnings-as-json")
+                .long("warnings-as-json")
+                .help("Print conversion warnings to stderr as a JSON array"),
+        )
+        .arg(
+            Arg::with_name("selftest")
+                .long("selftest")
+                .help("Transpile a bundled example contract to check the installation"),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .help("Report the files that would be written without writing them"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .help("With --dry-run, also print the generated Clarity code"),
+        )
         .get_matches();
 
+    if matches.is_present("selftest") {
+        print!("{}", sol2clarity::selftest()?);
+        println!("selftest passed");
+        return Ok(());
+    }
+
     let input_file = matches.value_of("INPUT").unwrap();
     let output_dir = matches
         .value_of("output")
         .map(String::from)
         .unwrap_or_else(|| String::from("."));
 
-    // Read input file
-    let source = fs::read_to_string(input_file)
-        .with_context(|| format!("Failed to read input file: {}", input_file))?;
+    let defaults = transpiler::TranspileOptions::default();
+    let max_string_length = match matches.value_of("max-string-length") {
+        Some(len) => len.parse()
+            .with_context(|| format!("Invalid --max-string-length: {}", len))?,
+        None => defaults.max_string_length,
+    };
+    let max_iterations = match matches.value_of("max-iterations") {
+        Some(n) => n.parse::<u32>().ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| anyhow!("Invalid --max-iterations: {} (expected a positive number)", n))?,
+        None => defaults.max_iterations,
+    };
+    let max_list_length = match matches.value_of("max-list-length") {
+        Some(len) => len.parse()
+            .with_context(|| format!("Invalid --max-list-length: {}", len))?,
+        None => defaults.max_list_length,
+    };
+    let indent = match matches.value_of("indent") {
+        Some(n) => n.parse()
+            .with_context(|| format!("Invalid --indent: {}", n))?,
+        None => defaults.indent,
+    };
+    let options = transpiler::TranspileOptions {
+        emit_comments: !matches.is_present("no-comments"),
+        max_string_length,
+        utf8_strings: matches.is_present("utf8"),
+        guard_init: matches.is_present("guard-init"),
+        max_iterations,
+        max_list_length,
+        indent,
+        reserved_names: match matches.value_of("reserved-names") {
+            Some("error") => transpiler::ReservedNamePolicy::Error,
+            _ => transpiler::ReservedNamePolicy::Rename,
+        },
+        optional_map_getters: matches.is_present("optional-getters"),
+        preserve_map_optionals: matches.is_present("preserve-map-optionals"),
+        membership_checks: matches.is_present("membership-checks"),
+        getters_return_response: !matches.is_present("raw-getters"),
+        event_envelope: matches.is_present("event-envelope"),
+        clarity_version: match matches.value_of("clarity-version") {
+            Some("1") => transpiler::ClarityVersion::Clarity1,
+            Some("3") => transpiler::ClarityVersion::Clarity3,
+            _ => transpiler::ClarityVersion::Clarity2,
+        },
+        source_name: Path::new(input_file)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()),
+        header_timestamp: matches.is_present("timestamp"),
+        strict: matches.is_present("strict"),
+        sort_definitions: matches.is_present("sort"),
+        group_by_visibility: matches.is_present("group-by-visibility"),
+        validate_inputs: matches.is_present("validate-inputs"),
+        optimize: matches.is_present("optimize"),
+        naming: match matches.value_of("naming") {
+            Some("preserve") => transpiler::NamingStyle::Preserve,
+            Some("snake") => transpiler::NamingStyle::Snake,
+            _ => transpiler::NamingStyle::Kebab,
+        },
+    };
 
-    // Parse Solidity code - now returns a Vec<Contract>
-    let contracts = parser::parse_all(&source)
-        .with_context(|| "Failed to parse Solidity code")?;
+    // Parse, convert and generate every contract in the file and its imports
+    let outputs = sol2clarity::transpile_file(Path::new(input_file), &options)?;
 
-    // Process each contract
-    for contract in contracts {
-        let contract_name = contract.name.clone();
+    if matches.is_present("warnings-as-json") {
+        eprint!("{}", sol2clarity::warnings_json(&outputs));
+    } else {
+        for output in &outputs {
+            for warning in &output.warnings {
+                eprintln!("{}: {}", output.name, warning);
+            }
+        }
+    }
+
+    let outputs = match matches.value_of("single-file") {
+        Some(name) => vec![sol2clarity::combine_contracts(name.trim_end_matches(".clar"), outputs)?],
+        None => outputs,
+    };
+
+    if let Some(manifest_path) = matches.value_of("emit-manifest") {
+        let manifest = error_manifest(&outputs);
+        if matches.is_present("dry-run") {
+            println!("would write {} ({} bytes)", manifest_path, manifest.len());
+        } else {
+            fs::write(manifest_path, manifest)
+                .with_context(|| format!("Failed to write manifest: {}", manifest_path))?;
+        }
+    }
+
+    // A dry run collects the Clarinet project's changes to report them all
+    let mut project = if matches.is_present("dry-run") && matches.is_present("clarinet") {
+        Some(clarinet::Project::open(Path::new(&output_dir))?)
+    } else {
+        None
+    };
 
-        // Convert to Clarity AST
-        let clarity_ast = transpiler::convert(contract)
-            .with_context(|| format!("Failed to convert {} to Clarity", contract_name))?;
+    for TranspiledContract { name: contract_name, code: clarity_code, .. } in outputs {
+        let file_stem = contract_name.to_lowercase();
 
-        // Generate Clarity code
-        let clarity_code = generator::generate(clarity_ast)
-            .with_context(|| format!("Failed to generate Clarity code for {}", contract_name))?;
+        if matches.is_present("dry-run") {
+            match project.as_mut() {
+                Some(project) => {
+                    project.add_contract(&file_stem, &clarity_code, options.clarity_version);
+                }
+                None => {
+                    let output_file = Path::new(&output_dir).join(format!("{}.clar", file_stem));
+                    println!("would write {} ({} bytes)", output_file.display(), clarity_code.len());
+                }
+            }
+            if matches.is_present("verbose") {
+                println!("{}", clarity_code);
+            }
+            continue;
+        }
 
-        // Create output file path
-        let output_file = Path::new(&output_dir)
-            .join(format!("{}.clar", contract_name.to_lowercase()));
+        let output_file = if matches.is_present("clarinet") {
+            clarinet::write_contract(Path::new(&output_dir), &file_stem, &clarity_code, options.clarity_version)?
+        } else {
+            // Create output file path
+            let output_file = Path::new(&output_dir)
+                .join(format!("{}.clar", file_stem));
 
-        // Write output file
-        fs::write(&output_file, clarity_code)
-            .with_context(|| format!("Failed to write output file: {}", output_file.display()))?;
+            // Write output file
+            fs::write(&output_file, clarity_code)
+                .with_context(|| format!("Failed to write output file: {}", output_file.display()))?;
+            output_file
+        };
 
         println!("Successfully converted {} to {}", contract_name, output_file.display());
     }
 
+    if let Some(project) = project {
+        for (path, contents) in project.pending() {
+            println!("would write {} ({} bytes)", path.display(), contents.len());
+        }
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// The error code manifest for the transpiled contracts. Codes are only
+/// unique within a contract, so several contracts are keyed by name.
+fn error_manifest(outputs: &[TranspiledContract]) -> String {
+    if let [output] = outputs {
+        return generator::generate_manifest(&output.error_codes);
+    }
+    let entries: Vec<String> = outputs.iter()
+        .map(|output| {
+            let manifest = generator::generate_manifest(&output.error_codes);
+            format!("  \"{}\": {}", output.name, manifest.trim_end().replace('\n', "\n  "))
+        })
+        .collect();
+    format!("{{\n{}\n}}\n", entries.join(",\n"))
+}
@@ -1,19 +1,33 @@
+use pest::error::LineColLocation;
 use pest::Parser;
 use pest_derive::Parser;
-use anyhow::{Result, Context, anyhow};
+use anyhow::{Result, anyhow};
+use std::sync::OnceLock;
 use crate::transpiler::ast::*;
+use crate::transpiler::converter::ZERO_ADDRESS_LITERAL;
 
 #[derive(Parser)]
 #[grammar = "parser/solidity.pest"]
 pub struct SolidityParser;
 
+/// Prints a parser trace line to stderr when `SOL2CLARITY_DEBUG` is set to
+/// anything but `0`. Tracing is off by default.
 fn debug_log(msg: &str) {
-    println!("[DEBUG] {}", msg);
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    if *ENABLED.get_or_init(|| std::env::var("SOL2CLARITY_DEBUG").is_ok_and(|value| !value.is_empty() && value != "0")) {
+        eprintln!("[DEBUG] {}", msg);
+    }
 }
 
 pub fn parse_all(source: &str) -> Result<Vec<Contract>> {
+    Ok(parse_source(source)?.contracts)
+}
+
+/// Parses a whole Solidity file, keeping its import paths and Solidity
+/// version pragma alongside the contracts.
+pub fn parse_source(source: &str) -> Result<SourceUnit> {
     let mut file_pairs = SolidityParser::parse(Rule::file, source)
-        .with_context(|| "Failed to parse Solidity contract, syntax error")?;
+        .map_err(|e| anyhow!(format_parse_error(&e)))?;
 
     // Get the file pair (should be the first and only one)
     let file_pair = file_pairs.next()
@@ -23,8 +37,24 @@ pub fn parse_all(source: &str) -> Result<Vec<Contract>> {
 
     // Parse all contracts
     let mut contracts = Vec::new();
+    let mut imports = Vec::new();
+    let mut pragma = None;
     for pair in file_pair.into_inner() {
         match pair.as_rule() {
+            Rule::pragma_directive => {
+                let value = pair.into_inner().next().map(|v| v.as_str().trim()).unwrap_or("");
+                if let Some(version) = value.strip_prefix("solidity") {
+                    debug_log(&format!("Found Solidity pragma: {}", version.trim()));
+                    pragma = Some(version.trim().to_string());
+                }
+            }
+            Rule::import_directive => {
+                if let Some(path) = pair.into_inner().find(|p| p.as_rule() == Rule::import_path) {
+                    let path = path.as_str().trim_matches('"').to_string();
+                    debug_log(&format!("Found import: {}", path));
+                    imports.push(path);
+                }
+            }
             Rule::contract_declaration => {
                 debug_log(&format!("Found contract declaration: {}", pair.as_str()));
                 let mut contract = Contract {
@@ -33,14 +63,33 @@ pub fn parse_all(source: &str) -> Result<Vec<Contract>> {
                     state_variables: Vec::new(),
                     events: Vec::new(),
                     constructor: None,
+                    base_contracts: Vec::new(),
+                    is_interface: false,
+                    is_library: false,
+                    structs: Vec::new(),
+                    enums: Vec::new(),
+                    using: Vec::new(),
+                    errors: Vec::new(),
+                    unsupported: Vec::new(),
+                    warnings: Vec::new(),
                 };
 
                 for item in pair.into_inner() {
                     match item.as_rule() {
+                        Rule::contract_kind => {
+                            contract.is_interface = item.as_str() == "interface";
+                            contract.is_library = item.as_str() == "library";
+                        }
                         Rule::identifier => {
                             contract.name = item.as_str().to_string();
                             debug_log(&format!("Found contract name: {}", contract.name));
                         }
+                        Rule::inheritance_specifier => {
+                            contract.base_contracts = item.into_inner()
+                                .map(|base| base.as_str().to_string())
+                                .collect();
+                            debug_log(&format!("Found base contracts: {:?}", contract.base_contracts));
+                        }
                         Rule::contract_body => {
                             parse_contract_body(&mut contract, item)?;
                         }
@@ -58,13 +107,97 @@ pub fn parse_all(source: &str) -> Result<Vec<Contract>> {
         return Err(anyhow!("No contract found in source"));
     }
 
-    Ok(contracts)
+    let mut duplicates: Vec<&str> = Vec::new();
+    for (i, contract) in contracts.iter().enumerate() {
+        let name = contract.name.as_str();
+        if contracts[..i].iter().any(|c| c.name == name) && !duplicates.contains(&name) {
+            duplicates.push(name);
+        }
+    }
+    if !duplicates.is_empty() {
+        return Err(anyhow!("Duplicate contract names: {}", duplicates.join(", ")));
+    }
+
+    Ok(SourceUnit {
+        imports,
+        pragma,
+        contracts,
+    })
 }
 
 
+/// Renders a pest error as `error at line L:C: <message>` followed by the
+/// offending source line and a caret under the failing column.
+fn format_parse_error(error: &pest::error::Error<Rule>) -> String {
+    let (line, column) = match error.line_col {
+        LineColLocation::Pos(pos) => pos,
+        LineColLocation::Span(start, _) => start,
+    };
+    Span { line, column, source_line: error.line().to_string() }.diagnostic(&error.variant.message())
+}
+
+/// Where `pair` starts in the source. `Pair::line_col` looks the line up
+/// in an index built once per parse; the position's own `line_col` and
+/// `line_of` scan the input, which is quadratic over a whole file.
+fn span_of(pair: &pest::iterators::Pair<Rule>) -> Span {
+    let (line, column) = pair.line_col();
+    let input = pair.as_span().get_input();
+    let start = pair.as_span().start();
+    let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+    Span { line, column, source_line: input[line_start..line_end].to_string() }
+}
+
 fn parse_contract_body(contract: &mut Contract, pair: pest::iterators::Pair<Rule>) -> Result<()> {
     for item in pair.into_inner() {
         match item.as_rule() {
+            Rule::struct_definition => {
+                debug_log(&format!("Parsing struct definition: {}", item.as_str()));
+                let name = item.clone().into_inner()
+                    .find(|p| p.as_rule() == Rule::identifier)
+                    .ok_or_else(|| anyhow!("Struct must have a name"))?
+                    .as_str().to_string();
+                contract.structs.push(Struct {
+                    name,
+                    fields: parse_parameters(item)?,
+                });
+            }
+            Rule::enum_definition => {
+                debug_log(&format!("Parsing enum definition: {}", item.as_str()));
+                let mut names = item.into_inner().map(|p| p.as_str().to_string());
+                let name = names.next()
+                    .ok_or_else(|| anyhow!("Enum must have a name"))?;
+                contract.enums.push(EnumDef {
+                    name,
+                    members: names.collect(),
+                });
+            }
+            Rule::using_for_directive => {
+                debug_log(&format!("Parsing using directive: {}", item.as_str()));
+                let mut tokens = item.into_inner();
+                let library = tokens.next()
+                    .ok_or_else(|| anyhow!("Using directive must name a library"))?
+                    .as_str().to_string();
+                let target = tokens.next()
+                    .ok_or_else(|| anyhow!("Using directive for {} must name a type", library))?;
+                let target = match target.into_inner().next() {
+                    Some(type_name) => Some(parse_type_name(type_name)?),
+                    None => None,
+                };
+                contract.using.push(UsingDirective { library, target });
+            }
+            Rule::error_definition => {
+                debug_log(&format!("Parsing error definition: {}", item.as_str()));
+                let name = item.clone().into_inner()
+                    .find(|p| p.as_rule() == Rule::identifier)
+                    .ok_or_else(|| anyhow!("Error must have a name"))?
+                    .as_str().to_string();
+                let params = match item.into_inner().find(|p| p.as_rule() == Rule::parameter_list) {
+                    Some(list) => parse_parameters(list)?,
+                    None => Vec::new(),
+                };
+                contract.errors.push(ErrorDef { name, params });
+            }
             Rule::state_variable_declaration => {
                 debug_log(&format!("Parsing state variable declaration: {}", item.as_str()));
                 if let Some(var) = parse_state_variable(item)? {
@@ -81,7 +214,7 @@ fn parse_contract_body(contract: &mut Contract, pair: pest::iterators::Pair<Rule
                         Rule::constructor_definition => {
                             constructor = parse_constructor(inner)?;
                         }
-                        Rule::regular_function_definition => {
+                        Rule::regular_function_definition | Rule::special_function_definition => {
                             function = parse_function(inner)?;
                         }
                         _ => {}
@@ -100,12 +233,32 @@ fn parse_contract_body(contract: &mut Contract, pair: pest::iterators::Pair<Rule
                     contract.events.push(event);
                 }
             }
+            Rule::unsupported_member => {
+                contract.unsupported.push(unsupported_note(item));
+            }
             _ => {}
         }
     }
     Ok(())
 }
 
+/// Describes an `unsupported_member` or `unsupported_statement` pair.
+fn unsupported_note(pair: pest::iterators::Pair<Rule>) -> UnsupportedNote {
+    let line = pair.line_col().0;
+    let construct = match pair.into_inner().next().map(|inner| inner.as_rule()) {
+        Some(Rule::modifier_definition) => "modifier definition",
+        Some(Rule::using_directive) => "using directive",
+        Some(Rule::assembly_statement) => "inline assembly",
+        Some(Rule::try_statement) => "try/catch",
+        _ => "construct",
+    };
+    debug_log(&format!("Skipping unsupported {} at line {}", construct, line));
+    UnsupportedNote {
+        construct: construct.to_string(),
+        line,
+    }
+}
+
 fn parse_state_variable(pair: pest::iterators::Pair<Rule>) -> Result<Option<StateVariable>> {
     let mut var = StateVariable {
         name: String::new(),
@@ -115,11 +268,14 @@ fn parse_state_variable(pair: pest::iterators::Pair<Rule>) -> Result<Option<Stat
         mapping_key_type: None,
         mapping_value_type: None,
         is_constant: false,
+        is_immutable: false,
         initial_value: None,
         nested_mapping: None,
+        span: Some(span_of(&pair)),
     };
 
-    let decl = pair.into_inner().next().unwrap();
+    let decl = pair.into_inner().next()
+        .ok_or_else(|| anyhow!("State variable declaration is empty"))?;
     match decl.as_rule() {
         Rule::basic_state_variable_declaration | Rule::mapping_state_variable_declaration => {
             for token in decl.into_inner() {
@@ -140,6 +296,9 @@ fn parse_state_variable(pair: pest::iterators::Pair<Rule>) -> Result<Option<Stat
                     Rule::visibility_modifier => {
                         var.visibility = Some(token.as_str().to_string());
                     }
+                    Rule::constant_modifier if token.as_str() == "immutable" => {
+                        var.is_immutable = true;
+                    }
                     Rule::constant_modifier => {
                         var.is_constant = true;
                     }
@@ -166,14 +325,22 @@ fn parse_state_variable(pair: pest::iterators::Pair<Rule>) -> Result<Option<Stat
 fn parse_mapping_type(pair: pest::iterators::Pair<Rule>) -> Result<(String, String, Option<Box<MappingType>>)> {
     let mut tokens = pair.into_inner();
 
-    let key_type = tokens.next()
-        .ok_or_else(|| anyhow!("Mapping key type not found"))?
-        .as_str().to_string();
+    let key = tokens.next()
+        .ok_or_else(|| anyhow!("Mapping key type not found"))?;
+    let key_type = key.as_str().to_string();
+    // Solidity keys are value types, strings or bytes; never arrays
+    if key_type.ends_with(']') {
+        return Err(anyhow!(span_of(&key).diagnostic(&format!(
+            "Invalid mapping key type {}: arrays cannot be mapping keys", key_type
+        ))));
+    }
 
     let value_type_token = tokens.next()
         .ok_or_else(|| anyhow!("Mapping value type not found"))?;
 
-    let type_token = value_type_token.into_inner().next().unwrap();
+    let type_token = value_type_token.into_inner().next()
+        .ok_or_else(|| anyhow!("Mapping value type is empty in mapping({} => ...)", key_type))?;
+    let span = span_of(&type_token);
     match type_token.as_rule() {
         Rule::mapping_type => {
             let (nested_key, nested_value, more_nested) = parse_mapping_type(type_token)?;
@@ -197,7 +364,7 @@ fn parse_mapping_type(pair: pest::iterators::Pair<Rule>) -> Result<(String, Stri
                 None
             ))
         }
-        _ => Err(anyhow!("Invalid mapping value type")),
+        _ => Err(anyhow!(span.diagnostic("Invalid mapping value type"))),
     }
 }
 
@@ -205,15 +372,18 @@ fn parse_function(pair: pest::iterators::Pair<Rule>) -> Result<Option<Function>>
     let mut function = Function {
         name: String::new(),
         params: Vec::new(),
-        return_type: None,
+        return_type: Vec::new(),
         visibility: None,
         mutability: None,
         body: Vec::new(),
+        implemented: false,
+        library: None,
+        span: Some(span_of(&pair)),
     };
 
     for token in pair.into_inner() {
         match token.as_rule() {
-            Rule::identifier => {
+            Rule::identifier | Rule::special_function_kind => {
                 function.name = token.as_str().to_string();
                 debug_log(&format!("Found function name: {}", function.name));
             }
@@ -226,22 +396,24 @@ fn parse_function(pair: pest::iterators::Pair<Rule>) -> Result<Option<Function>>
             Rule::state_mutability_modifier => {
                 function.mutability = Some(token.as_str().to_string());
             }
-            Rule::type_name => {
-                let inner = token.into_inner().next().unwrap();
-                match inner.as_rule() {
-                    Rule::basic_type => {
-                        function.return_type = Some(inner.as_str().to_string());
-                    }
-                    Rule::mapping_type => {
-                        let (key_type, value_type, _) = parse_mapping_type(inner)?;
-                        function.return_type = Some(format!("mapping({} => {})", key_type, value_type));
+            Rule::return_parameter_list => {
+                for param in token.into_inner() {
+                    let mut param_type = String::new();
+                    let mut param_name = None;
+                    for param_token in param.into_inner() {
+                        match param_token.as_rule() {
+                            Rule::type_name => param_type = parse_type_name(param_token)?,
+                            Rule::identifier => param_name = Some(param_token.as_str().to_string()),
+                            _ => {}
+                        }
                     }
-                    _ => {}
+                    function.return_type.push((param_name, param_type));
                 }
             }
             Rule::function_body => {
                 debug_log("Parsing function body");
                 function.body = parse_statements(token)?;
+                function.implemented = true;
                 debug_log(&format!("Found {} statements in function body", function.body.len()));
             }
             _ => {}
@@ -259,7 +431,9 @@ fn parse_constructor(pair: pest::iterators::Pair<Rule>) -> Result<Option<Constru
     let mut constructor = Constructor {
         params: Vec::new(),
         visibility: None,
+        base_calls: Vec::new(),
         body: Vec::new(),
+        span: Some(span_of(&pair)),
     };
 
     for token in pair.into_inner() {
@@ -270,6 +444,19 @@ fn parse_constructor(pair: pest::iterators::Pair<Rule>) -> Result<Option<Constru
             Rule::visibility_modifier => {
                 constructor.visibility = Some(token.as_str().to_string());
             }
+            Rule::base_constructor_call => {
+                let mut tokens = token.into_inner();
+                let base_name = tokens.next()
+                    .ok_or_else(|| anyhow!("Base constructor name not found"))?;
+                let mut args = Vec::new();
+                if let Some(arg_list) = tokens.next() {
+                    for arg in arg_list.into_inner() {
+                        args.push(parse_expression(arg)?);
+                    }
+                }
+                debug_log(&format!("Parsing base constructor call: {} with {} args", base_name.as_str(), args.len()));
+                constructor.base_calls.push((base_name.as_str().to_string(), args));
+            }
             Rule::function_body => {
                 constructor.body = parse_statements(token)?;
             }
@@ -301,8 +488,7 @@ fn parse_event(pair: pest::iterators::Pair<Rule>) -> Result<Option<Event>> {
                         for param_token in param.into_inner() {
                             match param_token.as_rule() {
                                 Rule::type_name => {
-                                    let type_token = param_token.into_inner().next().unwrap();
-                                    param_type = type_token.as_str().to_string();
+                                    param_type = parse_type_name(param_token)?;
                                 }
                                 Rule::indexed_modifier => {
                                     indexed = true;
@@ -329,6 +515,20 @@ fn parse_event(pair: pest::iterators::Pair<Rule>) -> Result<Option<Event>> {
     Ok(Some(event))
 }
 
+fn parse_type_name(pair: pest::iterators::Pair<Rule>) -> Result<String> {
+    let type_name = pair.as_str().to_string();
+    let type_token = pair.into_inner().next()
+        .ok_or_else(|| anyhow!("Type name {} has no type", type_name))?;
+    match type_token.as_rule() {
+        Rule::basic_type => Ok(type_token.as_str().to_string()),
+        Rule::mapping_type => {
+            let (key_type, value_type, _) = parse_mapping_type(type_token)?;
+            Ok(format!("mapping({} => {})", key_type, value_type))
+        }
+        _ => Err(anyhow!("Invalid type name: {}", type_token.as_str())),
+    }
+}
+
 fn parse_parameters(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Parameter>> {
     let mut params = Vec::new();
 
@@ -340,17 +540,7 @@ fn parse_parameters(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Parameter>>
             for token in param.into_inner() {
                 match token.as_rule() {
                     Rule::type_name => {
-                        let type_token = token.into_inner().next().unwrap();
-                        match type_token.as_rule() {
-                            Rule::basic_type => {
-                                param_type = type_token.as_str().to_string();
-                            }
-                            Rule::mapping_type => {
-                                let (key_type, value_type, _) = parse_mapping_type(type_token)?;
-                                param_type = format!("mapping({} => {})", key_type, value_type);
-                            }
-                            _ => {}
-                        }
+                        param_type = parse_type_name(token)?;
                     }
                     Rule::identifier => {
                         param_name = token.as_str().to_string();
@@ -373,33 +563,42 @@ fn parse_statements(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Statement>>
     let mut statements = Vec::new();
 
     for stmt in pair.into_inner() {
+        let span = span_of(&stmt);
+        let located = |kind| Statement { kind, span: Some(span.clone()) };
         match stmt.as_rule() {
             Rule::assignment_statement => {
                 let mut tokens = stmt.into_inner();
 
                 let index_access = tokens.next()
                     .ok_or_else(|| anyhow!("Assignment target not found"))?;
+                let operator = tokens.next()
+                    .ok_or_else(|| anyhow!("Assignment operator not found"))?;
                 let expr = tokens.next()
                     .ok_or_else(|| anyhow!("Assignment value not found"))?;
 
-                debug_log(&format!("Parsing assignment: {} = <expr>", index_access.as_str()));
+                debug_log(&format!("Parsing assignment: {} {} <expr>", index_access.as_str(), operator.as_str()));
 
+                let target_span = span_of(&index_access);
                 let target = parse_index_access(index_access)?;
+                let mut value = parse_expression(expr)?;
+                // `a += b` is `a = a + b`, which reads the old value first
+                if let Some(op) = operator.as_str().strip_suffix('=').filter(|op| !op.is_empty()) {
+                    value = Expression::BinaryOp(Box::new(target.clone()), op.to_string(), Box::new(value));
+                }
                 match target {
                     Expression::Identifier(id) => {
-                        statements.push(Statement::Assignment(
-                            id,
-                            parse_expression(expr)?
-                        ));
+                        statements.push(located(StatementKind::Assignment(id, value)));
                     }
                     Expression::MapAccess(map, key) => {
-                        statements.push(Statement::MapAccessAssignment(
-                            map,
-                            key,
-                            parse_expression(expr)?
-                        ));
+                        statements.push(located(StatementKind::MapAccessAssignment(map, key, value)));
                     }
-                    _ => return Err(anyhow!("Invalid assignment target")),
+                    Expression::MemberAccess(target, field) => match *target {
+                        Expression::MapAccess(map, key) => {
+                            statements.push(located(StatementKind::FieldAssignment(map, key, field, value)));
+                        }
+                        _ => return Err(anyhow!(target_span.diagnostic("Invalid assignment target"))),
+                    },
+                    _ => return Err(anyhow!(target_span.diagnostic("Invalid assignment target"))),
                 }
             }
             Rule::emit_statement => {
@@ -415,23 +614,86 @@ fn parse_statements(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Statement>>
                 }
 
                 debug_log(&format!("Parsing emit: {} with {} args", event_name.as_str(), args.len()));
-                statements.push(Statement::Emit(
+                statements.push(located(StatementKind::Emit(
                     event_name.as_str().to_string(),
                     args
-                ));
+                )));
             }
             Rule::return_statement => {
-                if let Some(expr) = stmt.into_inner().next() {
-                    debug_log("Parsing return statement with expression");
-                    statements.push(Statement::Return(parse_expression(expr)?));
+                let expr = stmt.into_inner().next().map(parse_expression).transpose()?;
+                debug_log(&format!("Parsing return statement (has expression: {})", expr.is_some()));
+                statements.push(located(StatementKind::Return(expr)));
+            }
+            Rule::revert_statement => {
+                let mut error_name = None;
+                let mut args = Vec::new();
+                for token in stmt.into_inner() {
+                    match token.as_rule() {
+                        Rule::identifier => error_name = Some(token.as_str().to_string()),
+                        Rule::argument_list => {
+                            for arg in token.into_inner() {
+                                args.push(parse_expression(arg)?);
+                            }
+                        }
+                        _ => {}
+                    }
                 }
+                debug_log(&format!("Parsing revert: {:?} with {} args", error_name, args.len()));
+                statements.push(located(StatementKind::Revert(error_name, args)));
+            }
+            Rule::delete_statement => {
+                let target = stmt.into_inner().next()
+                    .ok_or_else(|| anyhow!("Delete target not found"))?;
+                debug_log(&format!("Parsing delete: {}", target.as_str()));
+                statements.push(located(StatementKind::Delete(parse_index_access(target)?)));
+            }
+            Rule::while_statement => {
+                let mut tokens = stmt.into_inner();
+                let condition = tokens.next()
+                    .ok_or_else(|| anyhow!("While condition not found"))?;
+                let body = tokens.next()
+                    .ok_or_else(|| anyhow!("While body not found"))?;
+
+                debug_log("Parsing while loop");
+                statements.push(located(StatementKind::While(
+                    parse_expression(condition)?,
+                    parse_statements(body)?
+                )));
+            }
+            Rule::block => {
+                debug_log("Parsing nested block");
+                statements.push(located(StatementKind::Block(parse_statements(stmt)?)));
             }
             Rule::expression_statement => {
                 if let Some(expr) = stmt.into_inner().next() {
                     debug_log("Parsing expression statement");
-                    statements.push(Statement::Expression(parse_expression(expr)?));
+                    statements.push(located(StatementKind::Expression(parse_expression(expr)?)));
                 }
             }
+            Rule::local_variable_declaration => {
+                let mut var_type = String::new();
+                let mut name = String::new();
+                let mut value = None;
+                for token in stmt.into_inner() {
+                    match token.as_rule() {
+                        Rule::type_name => var_type = parse_type_name(token)?,
+                        Rule::identifier => name = token.as_str().to_string(),
+                        Rule::expression => value = Some(parse_expression(token)?),
+                        _ => {}
+                    }
+                }
+                debug_log(&format!("Parsing local variable declaration: {} {}", var_type, name));
+                statements.push(located(StatementKind::LocalVar(name, var_type, value)));
+            }
+            Rule::assert_statement => {
+                let condition = stmt.into_inner().next()
+                    .ok_or_else(|| anyhow!("Assert condition not found"))?;
+                debug_log("Parsing assert statement");
+                statements.push(located(StatementKind::Assert(parse_expression(condition)?)));
+            }
+            Rule::unsupported_statement => {
+                statements.push(located(StatementKind::Unsupported(unsupported_note(stmt))));
+            }
             _ => {}
         }
     }
@@ -444,34 +706,98 @@ fn parse_expression(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
     debug_log(&format!("Parsing expression: {}", pair.as_str()));
     match pair.as_rule() {
         Rule::expression => {
-            let mut tokens = pair.into_inner().peekable();
+            let mut tokens = pair.into_inner();
 
-            let first = tokens.next()
+            let condition = tokens.next()
                 .ok_or_else(|| anyhow!("Expression must have at least one term"))?;
-            let mut expr = parse_term(first)?;
-
-            while let Some(op) = tokens.next() {
-                if let Some(term) = tokens.next() {
-                    debug_log(&format!("Found binary operator: {}", op.as_str()));
-                    expr = Expression::BinaryOp(
-                        Box::new(expr),
-                        op.as_str().to_string(),
-                        Box::new(parse_term(term)?)
-                    );
+            let condition = parse_binary_expression(condition)?;
+
+            match (tokens.next(), tokens.next()) {
+                (Some(then_expr), Some(else_expr)) => {
+                    debug_log("Found ternary expression");
+                    Ok(Expression::Ternary(
+                        Box::new(condition),
+                        Box::new(parse_expression(then_expr)?),
+                        Box::new(parse_expression(else_expr)?)
+                    ))
                 }
+                _ => Ok(condition),
             }
-            Ok(expr)
         }
-        Rule::term | Rule::primary => parse_term(pair),
+        Rule::binary_expression => parse_binary_expression(pair),
         _ => parse_term(pair),
     }
 }
 
+/// Binding strength of a binary operator; higher binds tighter.
+fn operator_precedence(op: &str) -> u8 {
+    match op {
+        "*" | "/" | "%" => 11,
+        "+" | "-" => 10,
+        "<<" | ">>" => 9,
+        "&" => 8,
+        "^" => 7,
+        "|" => 6,
+        ">" | "<" | ">=" | "<=" => 5,
+        "==" | "!=" => 4,
+        "&&" => 3,
+        "||" => 2,
+        _ => 1,
+    }
+}
+
+fn parse_binary_expression(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+    let mut tokens = pair.into_inner();
+
+    let first = tokens.next()
+        .ok_or_else(|| anyhow!("Expression must have at least one term"))?;
+    let mut operands = vec![parse_term(first)?];
+    let mut operators: Vec<String> = Vec::new();
+
+    // Shunting-yard: reduce while the stacked operator binds at least as tightly
+    while let Some(op) = tokens.next() {
+        let term = tokens.next()
+            .ok_or_else(|| anyhow!("Missing operand after operator {}", op.as_str()))?;
+        let op = op.as_str().to_string();
+        debug_log(&format!("Found binary operator: {}", op));
+
+        while operators.last().is_some_and(|top| operator_precedence(top) >= operator_precedence(&op)) {
+            reduce_binary(&mut operands, &mut operators)?;
+        }
+        operators.push(op);
+        operands.push(parse_term(term)?);
+    }
+
+    while !operators.is_empty() {
+        reduce_binary(&mut operands, &mut operators)?;
+    }
+
+    operands.pop().ok_or_else(|| anyhow!("Empty expression"))
+}
+
+fn reduce_binary(operands: &mut Vec<Expression>, operators: &mut Vec<String>) -> Result<()> {
+    let op = operators.pop().ok_or_else(|| anyhow!("Missing operator"))?;
+    let right = operands.pop().ok_or_else(|| anyhow!("Missing right operand for {}", op))?;
+    let left = operands.pop().ok_or_else(|| anyhow!("Missing left operand for {}", op))?;
+    operands.push(Expression::BinaryOp(Box::new(left), op, Box::new(right)));
+    Ok(())
+}
+
 fn parse_term(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
     debug_log(&format!("Parsing term: {}", pair.as_str()));
     match pair.as_rule() {
         Rule::index_access => parse_index_access(pair),
-        Rule::literal => Ok(Expression::Literal(pair.as_str().to_string())),
+        Rule::function_call => parse_function_call(pair),
+        Rule::cast_expression => parse_cast(pair),
+        Rule::literal => {
+            // Normalize `1   days` to `1 days` so the unit is easy to split off
+            let text = pair.as_str();
+            if text.starts_with('"') {
+                Ok(Expression::Literal(text.to_string()))
+            } else {
+                Ok(Expression::Literal(text.split_whitespace().collect::<Vec<_>>().join(" ")))
+            }
+        }
         Rule::primary => {
             let inner = pair.into_inner().next()
                 .ok_or_else(|| anyhow!("Invalid primary expression"))?;
@@ -482,10 +808,104 @@ fn parse_term(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
                 .ok_or_else(|| anyhow!("Invalid term"))?;
             parse_term(inner)
         }
+        Rule::expression => parse_expression(pair),
+        Rule::tuple_expression => {
+            let elements = pair.into_inner()
+                .map(parse_expression)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Expression::Tuple(elements))
+        }
+        Rule::array_literal => {
+            let elements = pair.into_inner()
+                .map(parse_expression)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Expression::ArrayLiteral(elements))
+        }
+        Rule::unary_expression => {
+            let mut tokens = pair.into_inner();
+            let op = tokens.next()
+                .ok_or_else(|| anyhow!("Unary operator not found"))?;
+            let operand = tokens.next()
+                .ok_or_else(|| anyhow!("Operand not found for unary {}", op.as_str()))?;
+            Ok(Expression::UnaryOp(
+                op.as_str().to_string(),
+                Box::new(parse_term(operand)?)
+            ))
+        }
         _ => Err(anyhow!("Unexpected term type: {:?}", pair.as_rule())),
     }
 }
 
+/// Parses a `cast_expression` or `cast_callee`: a conversion such as
+/// `uint8(x)` or `payable(to)`, followed by any field accesses.
+fn parse_cast(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+    let mut tokens = pair.into_inner();
+    let target_type = tokens.next()
+        .ok_or_else(|| anyhow!("Expected type in cast"))?
+        .as_str().to_string();
+    let inner = parse_expression(tokens.next()
+        .ok_or_else(|| anyhow!("Expected expression in cast"))?)?;
+
+    // address(0) is the zero address rather than a conversion
+    if let ("address", Expression::Literal(value)) = (target_type.as_str(), &inner) {
+        if value == "0" {
+            return Ok(Expression::Literal(ZERO_ADDRESS_LITERAL.to_string()));
+        }
+    }
+    debug_log(&format!("Found cast to {}", target_type));
+    let mut expr = Expression::Cast(target_type, Box::new(inner));
+    for field in tokens {
+        let name = field.into_inner().next()
+            .ok_or_else(|| anyhow!("Expected field name"))?;
+        expr = Expression::MemberAccess(Box::new(expr), name.as_str().to_string());
+    }
+    Ok(expr)
+}
+
+fn parse_function_call(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+    let mut tokens = pair.into_inner();
+
+    let callee = tokens.next()
+        .ok_or_else(|| anyhow!("Expected callee in function call"))?;
+    let callee = match callee.as_rule() {
+        Rule::cast_callee => parse_cast(callee)?,
+        Rule::indexed_callee => parse_index_access(callee)?,
+        _ => parse_member_access(callee)?,
+    };
+
+    let mut args = Vec::new();
+    let mut method_calls = Vec::new();
+    for token in tokens {
+        match token.as_rule() {
+            Rule::argument_list => args = parse_arguments(token)?,
+            Rule::method_call => method_calls.push(token),
+            _ => {}
+        }
+    }
+
+    debug_log(&format!("Found call to {} with {} args", callee, args.len()));
+    let mut call = Expression::FunctionCall(Box::new(callee), args);
+    // Each chained call is made on the result of the previous one
+    for method_call in method_calls {
+        let mut tokens = method_call.into_inner();
+        let method = tokens.next()
+            .ok_or_else(|| anyhow!("Expected method name in chained call"))?;
+        let args = match tokens.next() {
+            Some(arg_list) => parse_arguments(arg_list)?,
+            None => Vec::new(),
+        };
+        call = Expression::FunctionCall(
+            Box::new(Expression::MemberAccess(Box::new(call), method.as_str().to_string())),
+            args
+        );
+    }
+    Ok(call)
+}
+
+fn parse_arguments(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Expression>> {
+    pair.into_inner().map(parse_expression).collect()
+}
+
 fn parse_index_access(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
     let mut tokens = pair.into_inner();
 
@@ -495,6 +915,12 @@ fn parse_index_access(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
 
     // Process any array/map access expressions that follow
     for access in tokens {
+        if access.as_rule() == Rule::field_access {
+            let field = access.into_inner().next()
+                .ok_or_else(|| anyhow!("Expected field name"))?;
+            expr = Expression::MemberAccess(Box::new(expr), field.as_str().to_string());
+            continue;
+        }
         if let Some(index_expr) = access.into_inner().next() {
             let index = parse_expression(index_expr)?;
             match expr {
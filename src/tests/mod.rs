@@ -1,10 +1,13 @@
+use super::clarinet;
 use super::parser;
 use super::generator;
 use super::transpiler::{ast::*, converter::*};
 use anyhow::Result;
 use crate::transpiler::converter::convert_solidity_type;
+use crate::transpiler::TranspileOptions;
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use super::*;
 
@@ -55,7 +58,7 @@ mod tests {
         let contract = parser::parse_all(source)?.remove(0);
         let func = &contract.functions[0];
         assert_eq!(func.name, "getValue");
-        assert_eq!(func.return_type, Some("uint256".to_string()));
+        assert_eq!(func.return_type, vec![(None, "uint256".to_string())]);
         Ok(())
     }
 
@@ -74,8 +77,8 @@ mod tests {
         assert_eq!(func.name, "add");
 
         // Verify the function body contains an assignment with binary operation
-        match &func.body[0] {
-            Statement::Assignment(var_name, Expression::BinaryOp(left, op, right)) => {
+        match &func.body[0].kind {
+            StatementKind::Assignment(var_name, Expression::BinaryOp(left, op, right)) => {
                 assert_eq!(var_name, "x");
                 assert_eq!(op, "+");
                 match (left.as_ref(), right.as_ref()) {
@@ -105,34 +108,48 @@ mod tests {
                     mapping_value_type: None,
                     initial_value: None,
                     is_constant: false,
+                    is_immutable: false,
                     nested_mapping: None,
+                    span: None,
                 }
             ],
             functions: vec![
                 Function {
                     name: "increment".to_string(),
                     params: vec![],
-                    return_type: None,
+                    return_type: vec![],
                     visibility: Some("public".to_string()),
                     mutability: None,
                     body: vec![
-                        Statement::Assignment(
+                        StatementKind::Assignment(
                             "count".to_string(),
                             Expression::BinaryOp(
                                 Box::new(Expression::Identifier("count".to_string())),
                                 "+".to_string(),
                                 Box::new(Expression::Literal("1".to_string()))
                             )
-                        )
+                        ).into()
                     ],
+                    implemented: true,
+                    library: None,
+                    span: None,
                 }
             ],
             events: vec![],
             constructor: None,
+            base_contracts: vec![],
+            is_interface: false,
+            is_library: false,
+            structs: vec![],
+            enums: vec![],
+            using: vec![],
+            errors: vec![],
+            unsupported: vec![],
+            warnings: vec![],
         };
 
-        let clarity_contract = convert_contract(contract)?;
-        let clarity_code = generator::generate(clarity_contract)?;
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
 
         assert!(clarity_code.contains("(define-data-var count uint u0)"));
         assert!(clarity_code.contains("(define-public (increment)"));
@@ -198,11 +215,82 @@ mod tests {
 
     #[test]
     fn test_type_conversion() {
-        assert_eq!(convert_solidity_type("uint256"), "uint");
-        assert_eq!(convert_solidity_type("bool"), "bool");
-        assert_eq!(convert_solidity_type("address"), "principal");
-        assert_eq!(convert_solidity_type("string"), "string-ascii");
-        assert_eq!(convert_solidity_type("unknown"), "uint"); // default case
+        let options = TranspileOptions::default();
+        assert_eq!(convert_solidity_type("uint256", &options).to_string(), "uint");
+        assert_eq!(convert_solidity_type("bool", &options).to_string(), "bool");
+        assert_eq!(convert_solidity_type("address", &options).to_string(), "principal");
+        assert_eq!(convert_solidity_type("string", &options).to_string(), "(string-ascii 256)");
+        assert_eq!(convert_solidity_type("unknown", &options).to_string(), "uint"); // default case
+    }
+
+    #[test]
+    fn test_normalize_type() -> Result<()> {
+        use crate::transpiler::{normalize_type, ClarityType};
+        let options = TranspileOptions::default();
+        assert_eq!(normalize_type("uint8", &options)?, ClarityType::Uint);
+        assert_eq!(normalize_type("int64", &options)?, ClarityType::Int);
+        assert_eq!(normalize_type("bytes32", &options)?, ClarityType::Buff(32));
+        assert_eq!(normalize_type("bytes", &options)?, ClarityType::Buff(256));
+        assert_eq!(normalize_type("address", &options)?, ClarityType::Principal);
+        assert_eq!(normalize_type("string", &options)?, ClarityType::StringAscii(256));
+        assert_eq!(
+            normalize_type("bool[4]", &options)?,
+            ClarityType::List(4, Box::new(ClarityType::Bool))
+        );
+        assert_eq!(
+            normalize_type("uint256[][3]", &options)?,
+            ClarityType::List(3, Box::new(ClarityType::List(256, Box::new(ClarityType::Uint))))
+        );
+        assert_eq!(normalize_type("Order", &options)?, ClarityType::Unknown("Order".to_string()));
+        assert!(normalize_type("uint7", &options).is_err());
+        assert!(normalize_type("bytes33", &options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clarity_type_display() {
+        use crate::transpiler::ClarityType;
+        let nested = ClarityType::Tuple(vec![
+            ("owner".to_string(), ClarityType::Principal),
+            ("amounts".to_string(), ClarityType::List(10, Box::new(ClarityType::Uint))),
+            ("meta".to_string(), ClarityType::Tuple(vec![
+                ("memo".to_string(), ClarityType::Optional(Box::new(ClarityType::Buff(34)))),
+                ("label".to_string(), ClarityType::StringAscii(32)),
+            ])),
+        ]);
+        assert_eq!(
+            nested.to_string(),
+            "{owner: principal, amounts: (list 10 uint), meta: {memo: (optional (buff 34)), label: (string-ascii 32)}}"
+        );
+        let response = ClarityType::Response(
+            Box::new(ClarityType::List(5, Box::new(ClarityType::Tuple(vec![("id".to_string(), ClarityType::Int)])))),
+            Box::new(ClarityType::Uint),
+        );
+        assert_eq!(response.to_string(), "(response (list 5 {id: int}) uint)");
+    }
+
+    #[test]
+    fn test_unknown_declared_type_is_reported() -> Result<()> {
+        let source = r#"
+            contract Router {
+                IERC20 token;
+                bytes32 root;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract.clone(), &Default::default())?;
+        assert_eq!(clarity_contract.warnings.len(), 1);
+        assert_eq!(clarity_contract.warnings[0].message, "unknown type IERC20 in Router was converted to uint");
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var root (buff 32) 0x)"));
+
+        let options = TranspileOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("Unknown type IERC20"));
+        Ok(())
     }
 
     #[test]
@@ -234,16 +322,16 @@ mod tests {
         assert_eq!(contract.functions[2].visibility, Some("internal".to_string()));
         assert_eq!(contract.functions[3].visibility, Some("external".to_string()));
 
-        let clarity_contract = convert_contract(contract)?;
-        let clarity_code = generator::generate(clarity_contract)?;
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
 
         // Public and external functions should be define-public
-        assert!(clarity_code.contains("(define-public (publicFunc)"));
-        assert!(clarity_code.contains("(define-public (externalFunc)"));
+        assert!(clarity_code.contains("(define-public (public-func)"));
+        assert!(clarity_code.contains("(define-public (external-func)"));
 
         // Private and internal functions should be define-private
-        assert!(clarity_code.contains("(define-private (privateFunc)"));
-        assert!(clarity_code.contains("(define-private (internalFunc)"));
+        assert!(clarity_code.contains("(define-private (private-func)"));
+        assert!(clarity_code.contains("(define-private (internal-func)"));
 
         Ok(())
     }
@@ -273,8 +361,8 @@ mod tests {
         }
 
         // Check generated Clarity code
-        let clarity_contract = convert_contract(contract)?;
-        let clarity_code = generator::generate(clarity_contract)?;
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
 
         // Public variables should have a getter function
         assert!(clarity_code.contains("(define-read-only (get-count)"));
@@ -326,8 +414,8 @@ mod tests {
         assert_eq!(token_owners.visibility.as_ref().unwrap(), "public");
 
         // Check generated Clarity code
-        let clarity_contract = convert_contract(contract)?;
-        let clarity_code = generator::generate(clarity_contract)?;
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
 
         // Verify balances map
         assert!(clarity_code.contains("(define-map balances principal uint)"));
@@ -345,4 +433,2566 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_custom_options_omit_comments() -> Result<()> {
+        let source = r#"
+            contract Test {
+                uint256 public count;
+                function increment() public {
+                    count = count + 1;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            emit_comments: false,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = crate::transpiler::convert(vec![contract], &options)?.remove(0);
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+
+        assert!(!clarity_code.contains(";; @desc"));
+        assert!(!clarity_code.contains(";; Function:"));
+        assert!(clarity_code.contains("(define-data-var count uint u0)"));
+        assert!(clarity_code.contains("(define-public (increment)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_kebab_case() {
+        let cases = [
+            ("getValue", "get-value"),
+            ("USDCToken", "usdc-token"),
+            ("getUSDCBalance", "get-usdc-balance"),
+            ("token2Owner", "token2-owner"),
+            ("tokenOwners", "token-owners"),
+            ("MAX_SUPPLY", "max-supply"),
+            ("snake_case_name", "snake-case-name"),
+            ("already-kebab", "already-kebab"),
+            ("count", "count"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(generator::to_kebab_case(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_emit_event_as_tuple_print() -> Result<()> {
+        let source = r#"
+            contract Token {
+                event Transfer(address indexed from, address indexed to, uint256 amount);
+
+                function send(address to) public {
+                    emit Transfer(msg.sender, to, 100);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+
+        assert!(clarity_code.contains("(print {event: \"Transfer\", from: tx-sender, to: "));
+        assert!(clarity_code.contains("amount: u100})"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ternary_expression() -> Result<()> {
+        let source = r#"
+            contract Test {
+                uint256 x;
+                function clamp() public returns (uint256) {
+                    return x > 0 ? x : 0;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        match &contract.functions[0].body[0].kind {
+            StatementKind::Return(Some(Expression::Ternary(cond, _, _))) => {
+                assert!(matches!(cond.as_ref(), Expression::BinaryOp(_, op, _) if op == ">"));
+            }
+            other => panic!("Expected ternary return, got {:?}", other),
+        }
+
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(if (> (var-get x) u0) (var-get x) u0)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_operator_precedence() -> Result<()> {
+        let source = r#"
+            contract Test {
+                uint256 x;
+                function f(uint256 a, uint256 b) {
+                    x = a + b * 2;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        match &contract.functions[0].body[0].kind {
+            StatementKind::Assignment(_, expr) => assert_eq!(expr.to_string(), "(a + (b * 2))"),
+            other => panic!("Expected assignment, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unary_expressions() -> Result<()> {
+        let source = r#"
+            contract Test {
+                bool active;
+                uint256 count;
+                function toggle() public {
+                    active = !active;
+                }
+                function negated() public returns (int256) {
+                    return -count;
+                }
+                function negativeLiteral() public returns (int256) {
+                    return -5;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        match &contract.functions[0].body[0].kind {
+            StatementKind::Assignment(_, Expression::UnaryOp(op, _)) => assert_eq!(op, "!"),
+            other => panic!("Expected unary assignment, got {:?}", other),
+        }
+
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(var-set active (not (var-get active)))"));
+        assert!(clarity_code.contains("(ok (- 0 (var-get count)))"));
+        assert!(clarity_code.contains("(ok -5)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_external_call_to_contract_call() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                function payout(address recipient, uint256 amount) public {
+                    erc20.transfer(recipient, amount);
+                    erc20.transferFrom(recipient, recipient, amount);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        match &contract.functions[0].body[0].kind {
+            StatementKind::Expression(Expression::FunctionCall(callee, args)) => {
+                assert_eq!(callee.to_string(), "erc20.transfer");
+                assert_eq!(args.len(), 2);
+            }
+            other => panic!("Expected call expression, got {:?}", other),
+        }
+
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        // A call made for its effect aborts the caller if it fails
+        assert!(clarity_code.contains("(try! (contract-call? .erc20 transfer recipient amount))"));
+        assert!(clarity_code.contains("(try! (contract-call? .erc20 transfer-from recipient recipient amount))"));
+        // Nothing defines an `erc20-trait`, so none is imported
+        assert!(!clarity_code.contains("use-trait"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_inheritance_merges_base_members() -> Result<()> {
+        let source = r#"
+            contract Ownable {
+                address public owner;
+                function transferOwnership(address newOwner) public {
+                    owner = newOwner;
+                }
+            }
+
+            contract Token is Ownable, Missing {
+                uint256 public totalSupply;
+            }
+        "#;
+        let contracts = parser::parse_all(source)?;
+        assert_eq!(contracts[1].base_contracts, vec!["Ownable".to_string(), "Missing".to_string()]);
+
+        let contracts = crate::transpiler::resolve_inheritance(contracts);
+        let token = &contracts[1];
+        let names: Vec<&str> = token.state_variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["owner", "totalSupply"]);
+        assert_eq!(token.functions[0].name, "transferOwnership");
+
+        let clarity_contract = convert_contract(contracts[1].clone(), &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var owner principal tx-sender)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_implemented_interface_functions_are_not_stubbed() -> Result<()> {
+        let source = r#"
+            interface ICounter {
+                event Bumped(uint256 count);
+                function bump() external;
+                function count() external view returns (uint256);
+            }
+
+            contract Counter is ICounter {
+                uint256 total;
+                function bump() external {
+                    total += 1;
+                    emit Bumped(total);
+                }
+            }
+        "#;
+        let contracts = crate::transpiler::resolve_inheritance(parser::parse_all(source)?);
+        let names: Vec<&str> = contracts[1].functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["bump"]);
+        // The interface keeps its declarations for its trait
+        assert_eq!(contracts[0].functions.len(), 2);
+
+        let clarity_code = generator::generate(convert_contract(contracts[1].clone(), &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(print {event: \"Bumped\", count: (var-get total)})"));
+        assert!(!clarity_code.contains("(ok u0)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sized_string_types() -> Result<()> {
+        let source = r#"
+            contract Profile {
+                string name;
+                string greeting = "hello";
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var name (string-ascii 256) \"\")"));
+        assert!(clarity_code.contains("(define-data-var greeting (string-ascii 256) \"hello\")"));
+
+        let options = TranspileOptions {
+            utf8_strings: true,
+            max_string_length: 64,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(define-data-var name (string-utf8 64) u\"\")"));
+        assert!(clarity_code.contains("(define-data-var greeting (string-utf8 64) u\"hello\")"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clarinet_layout_is_idempotent() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("sol2clarity-clarinet-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root)?;
+
+        clarinet::write_contract(&root, "counter", "(define-data-var count uint u0)\n", crate::transpiler::ClarityVersion::Clarity2)?;
+        clarinet::write_contract(&root, "counter", "(define-data-var count uint u1)\n", crate::transpiler::ClarityVersion::Clarity2)?;
+        clarinet::write_contract(&root, "token", "(define-data-var supply uint u0)\n", crate::transpiler::ClarityVersion::Clarity3)?;
+
+        let manifest = std::fs::read_to_string(root.join("Clarinet.toml"))?;
+        assert!(manifest.starts_with("[project]"));
+        assert_eq!(manifest.matches("[contracts.counter]").count(), 1);
+        assert!(manifest.contains("path = \"contracts/counter.clar\"\nclarity_version = 2\nepoch = 2.5\n"));
+        assert!(manifest.contains("path = \"contracts/token.clar\"\nclarity_version = 3\nepoch = 3.0\n"));
+
+        let source = std::fs::read_to_string(root.join("contracts/counter.clar"))?;
+        assert!(source.contains("u1"));
+        assert!(root.join("tests/counter_test.ts").exists());
+
+        std::fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_init_guard() -> Result<()> {
+        let source = r#"
+            contract Token {
+                uint256 supply;
+                constructor(uint256 initialSupply) {
+                    supply = 1000;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            guard_init: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+
+        assert!(clarity_code.contains("(define-data-var initialized bool false)"));
+        assert!(clarity_code.contains("(define-constant ERR_ALREADY_INITIALIZED (err u100))"));
+        assert!(clarity_code.contains("(asserts! (not (var-get initialized)) ERR_ALREADY_INITIALIZED)"));
+        assert!(clarity_code.contains("(var-set initialized true)"));
+
+        // Without the option the constructor is converted unguarded
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(!clarity_code.contains("initialized"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_source_directives() -> Result<()> {
+        let source = r#"
+            // SPDX-License-Identifier: MIT
+            pragma solidity ^0.8.0;
+            import "./Ownable.sol";
+            import {IERC20} from "./IERC20.sol";
+
+            contract Token {
+                uint256 supply;
+            }
+        "#;
+        let unit = parser::parse_source(source)?;
+        assert_eq!(unit.imports, vec!["./Ownable.sol".to_string(), "./IERC20.sol".to_string()]);
+        assert_eq!(unit.contracts.len(), 1);
+        assert_eq!(unit.contracts[0].name, "Token");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error_reports_location() {
+        let source = "contract Broken {\n    uint256 count;\n    function f() {\n        count = ;\n    }\n}\n";
+        let error = parser::parse_all(source).unwrap_err().to_string();
+        assert!(error.starts_with("error at line 4:"), "unexpected error: {}", error);
+        assert!(error.contains("count = ;"));
+        assert!(error.contains('^'));
+    }
+
+    #[test]
+    fn test_bounded_while_loop() -> Result<()> {
+        let source = r#"
+            contract Loop {
+                uint256 i;
+                function run() public {
+                    while (i < 3) {
+                        i = i + 1;
+                    }
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            max_iterations: 4,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.functions[0].body[0].kind, StatementKind::While(_, _)));
+
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+
+        assert!(clarity_code.contains("(define-constant LOOP_RANGE (list u0 u1 u2 u3))"));
+        assert!(clarity_code.contains("(define-private (run-while-0 (iteration uint) (running bool))"));
+        assert!(clarity_code.contains(
+            "(if (and running (< (var-get i) u3)) (begin (var-set i (+ (var-get i) u1)) true) false)"
+        ));
+        assert!(clarity_code.contains("(fold run-while-0 LOOP_RANGE true)\n    (ok true))"));
+        assert!(clarity_code.contains(";; WARNING: while loop lowered to a fold bounded to 4 iterations"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_while_loop_carries_locals_and_parameters() -> Result<()> {
+        let source = r#"
+            contract Loop {
+                uint256 total;
+                function sumTo(uint256 n) public {
+                    uint256 i = 0;
+                    uint256 acc = 0;
+                    while (i < n) {
+                        i = i + 1;
+                        acc = acc + i;
+                    }
+                    total = acc;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        // The step function sees the parameter and locals through its accumulator
+        assert!(clarity_code.contains(
+            "(define-private (sum-to-while-0 (iteration uint) (loop-state {running: bool, acc: uint, i: uint, n: uint}))"
+        ));
+        assert!(clarity_code.contains("(let ((acc (get acc loop-state)) (i (get i loop-state)) (n (get n loop-state)))"));
+        assert!(clarity_code.contains("{running: true, acc: acc-1, i: i-1, n: n}"));
+        assert!(clarity_code.contains("(merge loop-state {running: false})"));
+        // and the function continues with the values the loop left behind
+        assert!(clarity_code.contains("{running: true, acc: acc, i: i, n: n}))"));
+        assert!(clarity_code.contains("(acc-2 (get acc sum-to-while-0-state))"));
+        assert!(clarity_code.contains("(var-set total acc-2)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_while_loop_reads_function_locals() -> Result<()> {
+        let source = r#"
+            contract Loop {
+                uint256 total;
+                function fill() public {
+                    uint256 limit = 3;
+                    while (total < limit) {
+                        uint256 step = 1;
+                        total = total + step;
+                    }
+                    uint256 step = 2;
+                    total = total + step;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-private (fill-while-0 (iteration uint) (loop-state {running: bool, limit: uint}))"));
+        assert!(clarity_code.contains("(if (and (get running loop-state) (< (var-get total) limit))"));
+        // The body's step is scoped to one iteration and the unchanged
+        // limit needs no rebinding after the loop
+        assert!(clarity_code.contains("(fold fill-while-0 LOOP_RANGE {running: true, limit: limit})"));
+        assert!(!clarity_code.contains("fill-while-0-state"));
+        assert!(clarity_code.contains("(let ((step-1 u2)) (var-set total (+ (var-get total) step-1)))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_statements() -> Result<()> {
+        let source = r#"
+            contract Ledger {
+                uint256 count;
+                bool paused;
+                mapping(address => uint256) balances;
+                function reset() public {
+                    delete count;
+                    delete paused;
+                    delete balances[msg.sender];
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.functions[0].body[0].kind, StatementKind::Delete(Expression::Identifier(_))));
+
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(var-set count u0)"));
+        assert!(clarity_code.contains("(var-set paused false)"));
+        assert!(clarity_code.contains("(map-delete balances tx-sender)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_names_are_rejected() -> Result<()> {
+        let source = r#"
+            contract Foo {}
+            contract Foo {}
+        "#;
+        let error = parser::parse_all(source).unwrap_err().to_string();
+        assert!(error.contains("Duplicate contract names: Foo"), "unexpected error: {}", error);
+
+        let source = r#"
+            contract Bar {
+                uint256 x;
+                uint256 x;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let error = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        assert!(error.contains("Duplicate state variable names in Bar: x"), "unexpected error: {}", error);
+
+        let source = r#"
+            contract Baz {
+                function f() public {}
+                function f(uint256 a) public {}
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let error = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        assert!(error.contains("Duplicate function names in Baz: f"), "unexpected error: {}", error);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_address_comparison() -> Result<()> {
+        let source = r#"
+            contract Token {
+                address owner = address(0);
+                function isSet() public returns (bool) {
+                    return owner != address(0);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+
+        assert!(clarity_code.contains("(define-constant ZERO_ADDRESS 'SP000000000000000000002Q6VF78)"));
+        assert!(clarity_code.contains("(define-data-var owner principal ZERO_ADDRESS)"));
+        assert!(clarity_code.contains("(not (is-eq (var-get owner) ZERO_ADDRESS))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_annotation() -> Result<()> {
+        let source = r#"
+            contract Test {
+                uint256 value;
+                function getValue() public view returns (uint256) {
+                    return value;
+                }
+                function setValue() public returns (uint256) {
+                    value = 5;
+                }
+                function reset() public {
+                    value = 0;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        assert_eq!(clarity_contract.functions[0].return_type.as_deref(), Some("uint"));
+
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(";; @returns (response uint uint)\n(define-read-only (get-value)"));
+        assert!(clarity_code.contains(";; @returns (response bool uint)\n(define-public (reset)"));
+        // Falling off the end returns the zero value of the declared type
+        assert!(clarity_code.contains("(var-set value u5)\n    (ok u0))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiple_return_values() -> Result<()> {
+        let source = r#"
+            contract Test {
+                uint256 total;
+                function stats() public view returns (uint256 amount, bool active) {
+                    return (total, true);
+                }
+                function named() public view returns (uint256 count, bool ready) {
+                    count = total + 1;
+                    ready = true;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.functions[0].return_type, vec![
+            (Some("amount".to_string()), "uint256".to_string()),
+            (Some("active".to_string()), "bool".to_string()),
+        ]);
+
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        assert_eq!(clarity_contract.functions[0].return_type.as_deref(), Some("{amount: uint, active: bool}"));
+
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(ok {amount: (var-get total), active: true})"));
+        assert!(clarity_code.contains("(ok (let ((count (+ (var-get total) u1)) (ready true)) {count: count, ready: ready}))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_globals() -> Result<()> {
+        let source = r#"
+            contract Clock {
+                uint256 lastBlock;
+                uint256 lastTime;
+                function tick() public {
+                    lastBlock = block.number;
+                    lastTime = block.timestamp;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+
+        assert!(clarity_code.contains("(var-set last-block block-height)"));
+        assert!(clarity_code.contains(
+            "(var-set last-time (default-to u0 (get-block-info? time (- block-height u1))))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_globals_in_clarity3() -> Result<()> {
+        let source = r#"
+            contract Clock {
+                uint256 lastBlock;
+                uint256 lastTime;
+                address lastMiner;
+                function tick() public {
+                    lastBlock = block.number;
+                    lastTime = block.timestamp;
+                    lastMiner = block.coinbase;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            clarity_version: crate::transpiler::ClarityVersion::Clarity3,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+
+        assert!(clarity_code.contains("(var-set last-block stacks-block-height)"));
+        assert!(clarity_code.contains("(get-stacks-block-info? time (- stacks-block-height u1))"));
+        assert!(clarity_code.contains("(get-tenure-info? miner-address (- stacks-block-height u1))"));
+        assert!(!clarity_code.contains("get-block-info?"));
+        assert!(!clarity_code.contains("(- block-height"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_literal_forms() -> Result<()> {
+        assert_eq!(parse_number_literal("1_000"), Some(1000));
+        assert_eq!(parse_number_literal("0x10"), Some(16));
+        assert_eq!(parse_number_literal("100e18"), Some(100_000_000_000_000_000_000));
+        assert_eq!(parse_number_literal("\"abc\""), None);
+        assert_eq!(parse_number_literal("1e40"), None);
+
+        let source = r#"
+            contract Numbers {
+                uint256 supply = 1_000;
+                uint256 mask;
+                function setMask() public {
+                    mask = 0x10;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var supply uint u1000)"));
+        assert!(clarity_code.contains("(var-set mask u16)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_unit_suffixes() -> Result<()> {
+        let source = r#"
+            contract Vesting {
+                uint256 duration = 7 days;
+                uint256 price;
+                function setPrice() public {
+                    price = 2 ether;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var duration uint u604800)"));
+        assert!(clarity_code.contains("(var-set price u2000000000000000000)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_constant_folding() -> Result<()> {
+        let source = r#"
+            contract Folding {
+                uint256 x;
+                uint256 y;
+                function update() public {
+                    x = 1 + 2;
+                    y = x + 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(var-set x u3)"));
+        assert!(clarity_code.contains("(var-set y (+ (var-get x) u1))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_interface_generates_trait() -> Result<()> {
+        let source = r#"
+            interface IToken {
+                function transfer(address to, uint256 amount) external returns (bool);
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(contract.is_interface);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(define-trait token-trait ((transfer (principal uint) (response bool uint))))"
+        ));
+        assert!(!clarity_code.contains("define-public"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_valued_mapping() -> Result<()> {
+        let source = r#"
+            contract Registry {
+                struct User {
+                    address addr;
+                    uint256 balance;
+                }
+                mapping(address => User) users;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-map users principal {addr: principal, balance: uint})"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserved_names_are_renamed() -> Result<()> {
+        let source = r#"
+            contract Reserved {
+                uint256 map;
+                function list() public {
+                    map = map + 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var map-var uint u0)"));
+        assert!(clarity_code.contains("(var-set map-var (+ (var-get map-var) u1))"));
+        assert!(clarity_code.contains("(define-public (list-fn)"));
+
+        let contract = parser::parse_all(source)?.remove(0);
+        let options = TranspileOptions {
+            reserved_names: crate::transpiler::ReservedNamePolicy::Error,
+            ..Default::default()
+        };
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("map is a reserved word in Clarity"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_getter_defaults_missing_entries() -> Result<()> {
+        let source = r#"
+            contract Balances {
+                mapping(address => uint256) balances;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(ok (default-to u0 (map-get? balances key))))"));
+
+        let options = TranspileOptions {
+            optional_map_getters: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(ok (map-get? balances key)))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_builtins() -> Result<()> {
+        let source = r#"
+            contract Hashing {
+                uint256 data;
+                function digest() public view returns (uint256) {
+                    return keccak256(abi.encodePacked(data));
+                }
+                function encoded() public view returns (uint256) {
+                    return sha256(abi.encode(data));
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(keccak256 (var-get data))"));
+        assert!(clarity_code.contains("(sha256 (unwrap-panic (to-consensus-buff? (var-get data))))"));
+        assert!(clarity_code.contains(";; WARNING: abi.encodePacked in digest"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bool_literals_are_not_coerced() -> Result<()> {
+        let source = r#"
+            contract Flags {
+                bool active = true;
+                bool paused = 0;
+                uint256 count = 1;
+                function toggle() public {
+                    active = false;
+                    paused = 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var active bool true)"));
+        assert!(clarity_code.contains("(define-data-var paused bool false)"));
+        assert!(clarity_code.contains("(define-data-var count uint u1)"));
+        assert!(clarity_code.contains("(var-set active false)"));
+        assert!(clarity_code.contains("(var-set paused true)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clarity_version_gates_builtins() -> Result<()> {
+        let source = r#"
+            contract Lists {
+                uint256[10] values;
+                function first() public view returns (uint256) {
+                    return values[0];
+                }
+            }
+        "#;
+        let transpile = |version| -> Result<String> {
+            let options = TranspileOptions {
+                clarity_version: version,
+                ..Default::default()
+            };
+            let contract = parser::parse_all(source)?.remove(0);
+            generator::generate(convert_contract(contract, &options)?, &options)
+        };
+        let v1 = transpile(crate::transpiler::ClarityVersion::Clarity1)?;
+        let v2 = transpile(crate::transpiler::ClarityVersion::Clarity2)?;
+        assert!(v1.contains("(define-data-var values (list 10 uint) (list))"));
+        assert!(v1.contains("(unwrap-panic (element-at (var-get values) u0))"));
+        assert!(v2.contains("(unwrap-panic (element-at? (var-get values) u0))"));
+
+        let source = r#"
+            contract Encoding {
+                uint256 data;
+                function digest() public view returns (uint256) {
+                    return sha256(abi.encode(data));
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            clarity_version: crate::transpiler::ClarityVersion::Clarity1,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("to-consensus-buff? in digest requires Clarity2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_error_revert() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                error Unauthorized(address caller);
+                error InsufficientBalance();
+                function withdraw() public {
+                    revert Unauthorized(msg.sender);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.errors.len(), 2);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-constant ERR_UNAUTHORIZED u100)"));
+        assert!(clarity_code.contains("(define-constant ERR_INSUFFICIENT_BALANCE u101)"));
+        assert!(clarity_code.contains("(asserts! false (err ERR_UNAUTHORIZED))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_to_function_named_like_revert() -> Result<()> {
+        let source = r#"
+            contract Guard {
+                function revertIfZero(uint256 x) private pure {
+                    require(x != 0);
+                }
+                function check(uint256 x) public {
+                    revertIfZero(x);
+                    revert ();
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(&contract.functions[1].body[0].kind, StatementKind::Expression(Expression::FunctionCall(..))));
+        assert!(matches!(&contract.functions[1].body[1].kind, StatementKind::Revert(None, _)));
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(try! (revert-if-zero x))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calls_to_functions_named_like_keywords() -> Result<()> {
+        let source = r#"
+            contract Payouts {
+                uint256 total;
+                event Paid(uint256 amount);
+                function returnFunds(uint256 x) private {
+                    total -= x;
+                }
+                function emitter(uint256 x) private {
+                    emit Paid(x);
+                }
+                function deleteAll() private {
+                    delete total;
+                }
+                function run(uint256 x) public returns (uint256) {
+                    returnFunds(x);
+                    emitter(x);
+                    deleteAll();
+                    return total;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let run = &contract.functions[3];
+        assert!(run.body[..3].iter().all(|stmt| matches!(stmt.kind, StatementKind::Expression(Expression::FunctionCall(..)))));
+        assert!(matches!(&run.body[3].kind, StatementKind::Return(Some(_))));
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(try! (return-funds x))"));
+        assert!(clarity_code.contains("(try! (emitter x))"));
+        assert!(clarity_code.contains("(try! (delete-all))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_side_effects_end_with_ok_true() -> Result<()> {
+        let source = r#"
+            contract Pair {
+                uint256 a;
+                uint256 b;
+                function reset() public {
+                    a = 0;
+                    b = 0;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(begin\n    (var-set a u0)\n    (var-set b u0)\n    (ok true))"
+        ));
+        assert!(!clarity_code.contains("(ok (var-set"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_naming_styles() -> Result<()> {
+        let source = r#"
+            contract Bank {
+                mapping(address => uint256) balances;
+                function getUserBalance(address userAddress) public view returns (uint256) {
+                    return balances[userAddress];
+                }
+            }
+        "#;
+        let transpile = |naming| -> Result<String> {
+            let options = TranspileOptions {
+                naming,
+                ..Default::default()
+            };
+            let contract = parser::parse_all(source)?.remove(0);
+            generator::generate(convert_contract(contract, &options)?, &options)
+        };
+        let kebab = transpile(crate::transpiler::NamingStyle::Kebab)?;
+        let preserve = transpile(crate::transpiler::NamingStyle::Preserve)?;
+        let snake = transpile(crate::transpiler::NamingStyle::Snake)?;
+        assert!(kebab.contains("(get-user-balance (user-address principal))"));
+        assert!(preserve.contains("(getUserBalance (userAddress principal))"));
+        assert!(snake.contains("(get_user_balance (user_address principal))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_naming_applies_to_vars_and_maps() -> Result<()> {
+        let source = r#"
+            contract Users {
+                uint256 public userCount;
+                mapping(address => uint256) public userMap;
+                function register() public {
+                    userMap[msg.sender] = userCount;
+                    userCount = userCount + 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var user-count uint u0)"));
+        assert!(clarity_code.contains("(define-map user-map principal uint)"));
+        assert!(clarity_code.contains("(define-read-only (get-user-count)"));
+        assert!(clarity_code.contains("(define-read-only (get-user-map (key principal))"));
+        assert!(clarity_code.contains("(map-set user-map tx-sender (var-get user-count))"));
+        assert!(clarity_code.contains("(var-set user-count (+ (var-get user-count) u1))"));
+
+        let options = TranspileOptions {
+            naming: crate::transpiler::NamingStyle::Snake,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains("(define-data-var user_count uint u0)"));
+        assert!(clarity_code.contains("(define-map user_map principal uint)"));
+        assert!(clarity_code.contains("(define-read-only (get_user_count)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_and_event_fields_follow_naming_style() -> Result<()> {
+        let source = r#"
+            contract Registry {
+                struct UserInfo {
+                    uint256 totalBalance;
+                    bool isActive;
+                }
+                mapping(address => UserInfo) users;
+                event BalanceChanged(address indexed userAddress, uint256 newBalance);
+                function setBalance(uint256 newBalance) public {
+                    users[msg.sender].totalBalance = newBalance;
+                    emit BalanceChanged(msg.sender, users[msg.sender].totalBalance);
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            naming: crate::transpiler::NamingStyle::Snake,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains("(define-map users principal {total_balance: uint, is_active: bool})"));
+        assert!(clarity_code.contains("{total_balance: new_balance}"));
+        assert!(clarity_code.contains("user_address: tx-sender,"));
+        assert!(clarity_code.contains("new_balance: (get total_balance"));
+        assert!(!clarity_code.contains("total-balance"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_view_functions_are_read_only() -> Result<()> {
+        let source = r#"
+            contract Store {
+                uint256 x;
+                function getX() public view returns (uint256) {
+                    return x;
+                }
+                function setX() public {
+                    x = 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-read-only (get-x)"));
+        assert!(!clarity_code.contains("(define-public (get-x)"));
+        assert!(clarity_code.contains("(define-public (set-x)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_constant_getter() -> Result<()> {
+        let source = r#"
+            contract Limits {
+                uint256 public constant LIMIT = 100;
+                uint256 constant HIDDEN = 1;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-constant LIMIT u100)"));
+        assert!(clarity_code.contains("(define-read-only (get-limit)\n  (ok LIMIT))"));
+        assert!(!clarity_code.contains("get-hidden"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_casts_and_abi_decode() -> Result<()> {
+        let source = r#"
+            contract Casts {
+                uint256 balance;
+                uint256 small;
+                uint256 data;
+                function update() public {
+                    balance = uint256(balance);
+                    small = uint8(balance);
+                    balance = abi.decode(data, (uint256));
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(var-set balance (var-get balance))"));
+        assert!(clarity_code.contains("(var-set small (var-get balance))"));
+        assert!(clarity_code.contains(";; WARNING: uint8 cast in update is not range-checked"));
+        assert!(clarity_code.contains(
+            "(var-set balance (unwrap-panic (from-consensus-buff? uint (var-get data))))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_field_access_in_mapping() -> Result<()> {
+        let source = r#"
+            contract Registry {
+                struct User {
+                    address addr;
+                    uint256 balance;
+                }
+                mapping(address => User) users;
+                function credit(address who) public {
+                    users[who].balance = 10;
+                }
+                function balanceOf(address who) public view returns (uint256) {
+                    return users[who].balance;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.functions[0].body[0].kind, StatementKind::FieldAssignment(..)));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        let stored = "(default-to {addr: tx-sender, balance: u0} (map-get? users who))";
+        assert!(clarity_code.contains(&format!(
+            "(map-set users\n      who\n      (merge {} {{balance: u10}}))", stored
+        )));
+        assert!(clarity_code.contains(&format!("(ok (get balance {}))", stored)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_emit_validation() -> Result<()> {
+        let source = r#"
+            contract Events {
+                event Transfer(address from, address to, uint256 amount);
+                function undeclared() public {
+                    emit Approval(msg.sender);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err();
+        assert_eq!(err.to_string().lines().next(), Some("error at line 5:21: emit Approval: event is not declared"));
+
+        let source = r#"
+            contract Events {
+                event Transfer(address from, address to, uint256 amount);
+                function short(address to) public {
+                    emit Transfer(msg.sender, to);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err();
+        assert_eq!(err.to_string().lines().next(), Some("error at line 5:21: emit Transfer: expected 3 args, got 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_statement_setter() -> Result<()> {
+        let source = r#"
+            contract Setter {
+                uint256 value;
+                function setValue() public {
+                    value = 7;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-public (set-value)\n  (begin\n    (var-set value u7)\n    (ok true)))"));
+
+        // A side effect stays out of the ok payload even when the function
+        // is built by hand with a return type
+        let function = ClarityFunction {
+            name: "store".to_string(),
+            params: vec![],
+            public: true,
+            read_only: false,
+            return_type: Some("uint".to_string()),
+            wraps_response: true,
+            body: vec![ClarityExpression::FunctionCall(
+                "var-set".to_string(),
+                vec![ClarityExpression::Var("value".to_string()), ClarityExpression::Literal("u1".to_string())],
+            )],
+            notes: vec![],
+        };
+        let clarity_contract = ClarityContract {
+            name: "Setter".to_string(),
+            functions: vec![function],
+            data_vars: vec![],
+            maps: vec![],
+            events: vec![],
+            is_interface: false,
+            pragma: None,
+            error_codes: vec![],
+            traits: vec![],
+            warnings: vec![],
+        };
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(begin\n    (var-set value u1)\n    (ok true))"));
+        assert!(!clarity_code.contains("(ok (var-set"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_payable_and_contract_balance() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                function deposit() public payable {
+                }
+                function reserves() public view returns (uint256) {
+                    return this.balance;
+                }
+                function holdings() public view returns (uint256) {
+                    return address(this).balance;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.functions[0].mutability.as_deref(), Some("payable"));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(";; NOTE: deposit is payable in Solidity"));
+        assert!(clarity_code.contains("(define-read-only (reserves)\n  (ok (stx-get-balance (as-contract tx-sender))))"));
+        assert!(clarity_code.contains("(define-read-only (holdings)\n  (ok (stx-get-balance (as-contract tx-sender))))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_parameters() -> Result<()> {
+        let source = r#"
+            contract Batch {
+                function batch(uint256[] amounts) public {
+                }
+                function sum(uint256[] memory values) public {
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.functions[0].params[0].param_type, "uint256[]");
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-public (batch (amounts (list 256 uint)))"));
+        assert!(clarity_code.contains("(define-public (sum (values (list 256 uint)))"));
+
+        let options = TranspileOptions {
+            max_list_length: 10,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains("(amounts (list 10 uint))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_globals() -> Result<()> {
+        let source = r#"
+            contract Origin {
+                address caller;
+                uint256 price;
+                function record() public {
+                    caller = tx.origin;
+                    price = tx.gasprice;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(var-set caller tx-sender)"));
+        assert!(clarity_code.contains(";; WARNING: tx.origin in record became tx-sender"));
+        assert!(clarity_code.contains("(var-set price u0)"));
+        assert!(clarity_code.contains(";; WARNING: tx.gasprice in record has no Clarity equivalent"));
+
+        let options = TranspileOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("tx.gasprice in record has no Clarity equivalent"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mapping_read_in_comparison() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                mapping(address => uint256) balances;
+                function canWithdraw(address addr, uint256 amount) public view returns (bool) {
+                    return balances[addr] >= amount;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(>= (default-to u0 (map-get? balances"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_output_ignores_source_order() -> Result<()> {
+        let first = r#"
+            contract Ledger {
+                uint256 total;
+                mapping(address => uint256) credits;
+                address admin;
+                mapping(address => uint256) debits;
+                function settle() public { total = 0; }
+                function audit() public { total = 1; }
+            }
+        "#;
+        let second = r#"
+            contract Ledger {
+                mapping(address => uint256) debits;
+                address admin;
+                function audit() public { total = 1; }
+                mapping(address => uint256) credits;
+                function settle() public { total = 0; }
+                uint256 total;
+            }
+        "#;
+        let options = TranspileOptions {
+            sort_definitions: true,
+            ..Default::default()
+        };
+        let mut outputs = Vec::new();
+        for source in [first, second] {
+            let contract = parser::parse_all(source)?.remove(0);
+            let clarity_contract = convert_contract(contract, &options)?;
+            outputs.push(generator::generate(clarity_contract, &options)?);
+        }
+        assert_eq!(outputs[0], outputs[1]);
+        let admin = outputs[0].find("(define-data-var admin").unwrap();
+        let total = outputs[0].find("(define-data-var total").unwrap();
+        assert!(admin < total);
+        Ok(())
+    }
+
+    #[test]
+    fn test_require_error_codes() -> Result<()> {
+        let source = r#"
+            contract Guarded {
+                uint256 value;
+                function set(uint256 amount) public {
+                    require(amount > 0);
+                    require(amount < 1000);
+                    require(msg.sender != address(0), "zero sender");
+                    value = amount;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        assert_eq!(clarity_contract.error_codes, vec![
+            (1, "failed without a specific error".to_string()),
+            (100, "zero sender".to_string()),
+        ]);
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert_eq!(clarity_code.matches("(err u1))").count(), 2);
+        assert!(clarity_code.contains("(define-constant ERR_ZERO_SENDER u100)"));
+        assert!(clarity_code.contains("(err ERR_ZERO_SENDER))"));
+        assert_eq!(clarity_code.matches("(define-constant ERR_").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bare_return() -> Result<()> {
+        let source = r#"
+            contract Stopper {
+                uint256 count;
+                function stop() public {
+                    count = 1;
+                    return;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.functions[0].body.last().map(|stmt| &stmt.kind), Some(StatementKind::Return(None))));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(begin\n    (var-set count u1)\n    (ok true))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_return_inside_nested_block_ends_the_function() -> Result<()> {
+        let source = r#"
+            contract Stopper {
+                uint256 count;
+                function stop() public {
+                    count = 1;
+                    {
+                        uint256 next = 2;
+                        count = next;
+                        return;
+                    }
+                    count = 3;
+                }
+                function peek() public view returns (uint256 value) {
+                    {
+                        return count;
+                    }
+                    value = 7;
+                }
+                function spin() public {
+                    while (count < 3) {
+                        return;
+                    }
+                }
+            }
+        "#;
+        let mut contract = parser::parse_all(source)?.remove(0);
+        let spin = contract.functions.pop().unwrap();
+        let clarity_code = generator::generate(convert_contract(contract.clone(), &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(let ((next u2)) (var-set count next))"));
+        assert!(!clarity_code.contains("u3"));
+        assert!(clarity_code.contains("(define-read-only (peek)\n  (ok (var-get count)))"));
+
+        // A fold cannot leave the function early
+        contract.functions = vec![spin];
+        let error = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        assert!(error.contains("return inside a while loop in spin is not supported"), "unexpected error: {}", error);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_constructs_are_reported() -> Result<()> {
+        let source = r#"
+            contract Low {
+                using {square} for uint256;
+                uint256 value;
+                function raw() public {
+                    assembly {
+                        let x := add(1, 2)
+                    }
+                    value = 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.unsupported.len(), 1);
+        assert_eq!(contract.unsupported[0].construct, "using directive");
+        assert_eq!(contract.unsupported[0].line, 3);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(";; WARNING: line 6: inline assembly in raw is not supported"));
+        assert!(clarity_code.contains("(var-set value u1)"));
+
+        let options = TranspileOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("line 6: inline assembly in raw is not supported"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_immutable_state_variable() -> Result<()> {
+        let source = r#"
+            contract Owned {
+                address immutable owner;
+                constructor() {
+                    owner = msg.sender;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(contract.state_variables[0].is_immutable);
+        assert!(!contract.state_variables[0].is_constant);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-data-var owner principal tx-sender)"));
+        assert!(!clarity_code.contains("(define-constant owner"));
+        assert!(clarity_code.contains("(asserts! (not (var-get initialized)) ERR_ALREADY_INITIALIZED)"));
+        assert!(clarity_code.contains("(var-set owner tx-sender)"));
+
+        let source = r#"
+            contract Owned {
+                address immutable owner;
+                function steal() public {
+                    owner = msg.sender;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err();
+        assert!(err.to_string().contains("Immutable owner can only be assigned in the constructor"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_map_getter_takes_key_fields() -> Result<()> {
+        let source = r#"
+            contract Approvals {
+                mapping(address => mapping(uint256 => bool)) public approvals;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(define-read-only (get-approvals (owner principal) (token-id uint))\n  \
+             (ok (default-to false (map-get? approvals {owner: owner, token-id: token-id}))))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_mapping_key_is_an_error() {
+        let source = r#"
+            contract Bad {
+                mapping(uint256[] => bool) public seen;
+            }
+        "#;
+        let err = parser::parse_all(source).unwrap_err();
+        assert!(err.to_string().contains("Invalid mapping key type uint256[]"));
+    }
+
+    #[test]
+    fn test_unrepresentable_mapping_key_is_an_error() -> Result<()> {
+        let source = r#"
+            contract Registry {
+                struct Pair { address owner; uint256 id; }
+                mapping(Pair => bool) public known;
+                mapping(IERC20 => uint256) public limits;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported key type IERC20 for mapping limits"),
+            "unexpected error: {}", err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_inputs_guards_amounts() -> Result<()> {
+        let source = r#"
+            contract Bank {
+                uint256 total;
+                function deposit(uint256 amount, address to) public {
+                    total = total + amount;
+                }
+                function burn(uint256 amountIn) internal {
+                    total = total - amountIn;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            validate_inputs: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(define-constant ERR_ZERO_AMOUNT u100)"));
+        assert!(clarity_code.contains(
+            "(define-public (deposit (amount uint) (to principal))\n  (begin\n    (asserts! (> amount u0) (err ERR_ZERO_AMOUNT))"
+        ));
+        assert_eq!(clarity_code.matches("(asserts!").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_receive_function() -> Result<()> {
+        let source = r#"
+            contract Wallet {
+                uint256 deposits;
+                receive() external payable {
+                    deposits = deposits + 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.functions[0].name, "receive");
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(";; NOTE: converted from receive()"));
+        assert!(clarity_code.contains("(define-public (handle-deposit)"));
+        assert!(clarity_code.contains("(var-set deposits (+ (var-get deposits) u1))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_three_level_mapping_access() -> Result<()> {
+        let source = r#"
+            contract Deep {
+                mapping(address => mapping(address => mapping(uint256 => uint256))) allowances;
+                function approve(address spender, uint256 id, uint256 amount) public {
+                    allowances[msg.sender][spender][id] = amount;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(define-map allowances {key-1: principal, key-2: principal, key-3: uint} uint)"
+        ));
+        assert!(clarity_code.contains(
+            "(define-read-only (get-allowances (key-1 principal) (key-2 principal) (key-3 uint))"
+        ));
+        assert!(clarity_code.contains("(map-set allowances {key-1: tx-sender, key-2: spender, key-3: id} amount)"));
+        assert!(!clarity_code.contains("(tuple"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_interface_parameters_declare_traits_once() -> Result<()> {
+        let source = r#"
+            interface IToken {
+                function transfer(address to, uint256 amount) external returns (bool);
+                function approve(address spender, uint256 amount) external returns (bool);
+            }
+            contract Router {
+                function swap(IToken token, address to, uint256 amount) public {
+                    token.transfer(to, amount);
+                    token.approve(to, amount);
+                }
+                function refund(IToken token, address to) public {
+                    token.transfer(to, 1);
+                }
+            }
+        "#;
+        let contracts = parser::parse_all(source)?;
+        let clarity_contract = crate::transpiler::convert(contracts, &Default::default())?.remove(1);
+        assert_eq!(clarity_contract.traits.len(), 1);
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert_eq!(clarity_code.matches("(use-trait token-trait .itoken.token-trait)").count(), 1);
+        assert!(clarity_code.contains("(define-public (swap (token <token-trait>)"));
+        assert!(clarity_code.contains("(try! (contract-call? token approve to amount))"));
+        let use_trait = clarity_code.find("(use-trait").unwrap();
+        assert!(use_trait < clarity_code.find("(define-public (swap").unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_uses_panic_code() -> Result<()> {
+        let source = r#"
+            contract Supply {
+                uint256 total;
+                uint256 cap;
+                function mint(uint256 amount) public {
+                    require(amount < 100, "too much");
+                    total = total + amount;
+                    assert(total <= cap);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.functions[0].body[2].kind, StatementKind::Assert(_)));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-constant ERR_PANIC u2)"));
+        assert!(clarity_code.contains("(define-constant ERR_TOO_MUCH u100)"));
+        assert!(clarity_code.contains("(asserts! (<= (var-get total) (var-get cap)) (err ERR_PANIC))"));
+        assert!(clarity_code.contains("(err ERR_TOO_MUCH))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_local_variable_declaration() -> Result<()> {
+        let source = r#"
+            contract Adder {
+                uint256 result;
+                function add(uint256 a, uint256 b) public {
+                    uint256 total = a + b;
+                    uint256 doubled = total * 2;
+                    result = doubled;
+                }
+                function sum(uint256 a) public view returns (uint256) {
+                    uint256 total = a + 1;
+                    return total;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(&contract.functions[0].body[0].kind, StatementKind::LocalVar(name, ty, Some(_)) if name == "total" && ty == "uint256"));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(let ((total (+ a b)) (doubled (* total u2))) (var-set result doubled))"));
+        assert!(clarity_code.contains("(ok (let ((total (+ a u1))) total))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_optimize_hoists_repeated_map_reads() -> Result<()> {
+        let source = r#"
+            contract Ledger {
+                mapping(address => uint256) balances;
+                uint256 total;
+                function audit(address addr) public {
+                    require(balances[addr] > 10);
+                    total = total + balances[addr];
+                }
+                function credit(address addr) public {
+                    total = balances[addr];
+                    balances[addr] = balances[addr] + 1;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            optimize: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(let ((balances-entry (default-to u0 (map-get? balances addr))))"));
+        assert!(clarity_code.contains("(asserts! (> balances-entry u10) (err u1))"));
+        assert!(clarity_code.contains("(var-set total (+ (var-get total) balances-entry))"));
+        // credit writes the map, so its reads are left in place
+        assert_eq!(clarity_code.matches("balances-entry").count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_int_literals_are_signed() -> Result<()> {
+        let source = r#"
+            contract Thermometer {
+                int256 offset = -7;
+                int256 reading;
+                function calibrate() public {
+                    reading = -3;
+                    offset = 5;
+                }
+            }
+        "#;
+        let options = TranspileOptions::default();
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(define-data-var offset int -7)"));
+        assert!(clarity_code.contains("(define-data-var reading int 0)"));
+        assert!(clarity_code.contains("(var-set reading -3)"));
+        assert!(clarity_code.contains("(var-set offset 5)"));
+        assert!(!clarity_code.contains("u-3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_functions_by_visibility() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                uint256 total;
+                function helper() private { total = 0; }
+                function balance() public view returns (uint256) { return total; }
+                function deposit() public { total = total + 1; }
+                function peek() public view returns (uint256) { return total; }
+                function withdraw() public { total = total - 1; }
+            }
+        "#;
+        let options = TranspileOptions {
+            group_by_visibility: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        let order: Vec<usize> = [
+            ";; ---- Public Functions ----",
+            "(define-public (deposit",
+            "(define-public (withdraw",
+            ";; ---- Read-Only Functions ----",
+            "(define-read-only (balance",
+            "(define-read-only (peek",
+            ";; ---- Private Functions ----",
+            "(define-private (helper",
+        ].iter().map(|needle| clarity_code.find(needle).expect(needle)).collect();
+        assert!(order.windows(2).all(|pair| pair[0] < pair[1]), "unexpected order:\n{}", clarity_code);
+        Ok(())
+    }
+
+    #[test]
+    fn test_constructor_base_calls_run_base_init_first() -> Result<()> {
+        let source = r#"
+            contract Ownable {
+                address owner;
+                constructor(address initialOwner) {
+                    owner = initialOwner;
+                }
+            }
+
+            contract Token is Ownable {
+                uint256 supply;
+                constructor(uint256 initialSupply) Ownable(msg.sender) payable {
+                    supply = initialSupply;
+                }
+            }
+        "#;
+        let contracts = parser::parse_all(source)?;
+        let base_calls = &contracts[1].constructor.as_ref().expect("constructor").base_calls;
+        assert_eq!(base_calls.len(), 1);
+        assert_eq!(base_calls[0].0, "Ownable");
+
+        let token = crate::transpiler::resolve_inheritance(contracts).remove(1);
+        let clarity_code = generator::generate(convert_contract(token, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-public (init (initial-supply uint))"), "{}", clarity_code);
+        assert!(clarity_code.contains("(let ((initial-owner tx-sender))"), "{}", clarity_code);
+        let base_init = clarity_code.find("(var-set owner").expect("base init");
+        let own_init = clarity_code.find("(var-set supply").expect("own init");
+        assert!(base_init < own_init);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitwise_operators() -> Result<()> {
+        let source = r#"
+            contract Flags {
+                uint256 a;
+                uint256 b;
+                uint256 x;
+                function mix() public view returns (uint256) {
+                    return a & b | x ^ a;
+                }
+                function shift() public view returns (uint256) {
+                    return (x << 2) + (x >> 1) + ~a;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract.clone(), &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(bit-or (bit-and (var-get a) (var-get b)) (bit-xor (var-get x) (var-get a)))"));
+        assert!(clarity_code.contains("(bit-shift-left (var-get x) u2)"));
+        assert!(clarity_code.contains("(bit-shift-right (var-get x) u1)"));
+        assert!(clarity_code.contains("(bit-not (var-get a))"));
+
+        let options = TranspileOptions {
+            clarity_version: crate::transpiler::ClarityVersion::Clarity1,
+            ..Default::default()
+        };
+        let err = convert_contract(contract, &options).unwrap_err();
+        assert!(err.to_string().contains("bit-and in mix requires Clarity2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_this_is_the_contract_principal() -> Result<()> {
+        let source = r#"
+            contract Escrow {
+                address holder;
+                mapping(address => uint256) deposits;
+                function lock() public {
+                    holder = address(this);
+                    deposits[address(this)] = 1;
+                }
+                function isSelf(address who) public view returns (bool) {
+                    return who == this;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(var-set holder (as-contract tx-sender))"));
+        assert!(clarity_code.contains("(map-set deposits (as-contract tx-sender) u1)"));
+        assert!(clarity_code.contains("(is-eq who (as-contract tx-sender))"));
+        assert!(!clarity_code.contains("var-get this"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compound_map_assignment_reads_then_writes() -> Result<()> {
+        let source = r#"
+            contract Token {
+                mapping(address => uint256) balances;
+                uint256 supply;
+                function mint(address to, uint256 amount) public {
+                    balances[to] += amount;
+                    balances[msg.sender] -= 1;
+                    supply *= 2;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(map-set balances to (+ (default-to u0 (map-get? balances to)) amount))"
+        ));
+        assert!(clarity_code.contains("(map-set balances tx-sender (- (default-to u0 (map-get? balances tx-sender)) u1))"));
+        assert!(clarity_code.contains("(var-set supply (* (var-get supply) u2))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boolean_chains_are_flattened() -> Result<()> {
+        let source = r#"
+            contract Gate {
+                bool a;
+                bool b;
+                bool c;
+                function all() public view returns (bool) {
+                    return a && b && c;
+                }
+                function any() public view returns (bool) {
+                    return a || b && c || c;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(and (var-get a) (var-get b) (var-get c))"));
+        assert!(clarity_code.contains("(or (var-get a) (and (var-get b) (var-get c)) (var-get c))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_require_in_constructor_shares_error_codes() -> Result<()> {
+        let source = r#"
+            contract Token {
+                uint256 supply;
+                constructor(uint256 initialSupply) {
+                    require(initialSupply > 0, "supply must be positive");
+                    supply = initialSupply;
+                }
+                function burn(uint256 amount) public {
+                    require(amount <= supply, "insufficient supply");
+                    supply = supply - amount;
+                }
+            }
+        "#;
+        let options = TranspileOptions {
+            guard_init: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        let codes: Vec<u32> = clarity_contract.error_codes.iter().map(|(code, _)| *code).collect();
+        assert_eq!(codes, vec![100, 101, 102]);
+
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        let init = &clarity_code[clarity_code.find("(define-public (init").expect("init")..];
+        assert!(init.contains("(asserts! (> initial-supply u0) (err ERR_SUPPLY_MUST_BE_POSITIVE))"));
+        assert!(clarity_code.contains("(define-constant ERR_ALREADY_INITIALIZED (err u100))"));
+        assert!(clarity_code.contains("(define-constant ERR_SUPPLY_MUST_BE_POSITIVE u101)"));
+        assert!(clarity_code.contains("(define-constant ERR_INSUFFICIENT_SUPPLY u102)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_colliding_getters_are_an_error() -> Result<()> {
+        let source = r#"
+            contract Supply {
+                uint256 public totalSupply;
+                mapping(address => uint256) total_supply;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let err = generator::generate(clarity_contract, &Default::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Getter get-total-supply for map total-supply collides with the getter for variable total-supply"
+        );
+
+        let source = r#"
+            contract Counter {
+                uint256 public count;
+                function getCount() public view returns (uint256) {
+                    return count;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert_eq!(clarity_code.matches("(define-read-only (get-count)").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_blocks_are_inlined_with_fresh_names() -> Result<()> {
+        let source = r#"
+            contract Scopes {
+                uint256 public total;
+                function run() public returns (uint256) {
+                    uint256 x = 1;
+                    {
+                        uint256 y = x + 1;
+                        x = y * 2;
+                        {
+                            uint256 x = 10;
+                            total = x;
+                        }
+                    }
+                    uint256 y = x;
+                    return y;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        // The inner x shadows the outer one only until its block ends, and
+        // the later y cannot rebind the block's y
+        assert!(clarity_code.contains(
+            "(let ((x u1) (y (+ x u1)) (x-1 (* y u2)) (x-2 u10)) (var-set total x-2) (let ((y-1 x-1)) y-1))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_if_in_begin_is_indented_consistently() -> Result<()> {
+        let source = r#"
+            contract Pricing {
+                uint256 public lastSettlementPrice;
+                function settle(uint256 requestedPrice, uint256 oraclePrice) public {
+                    require(requestedPrice > 0, "zero price");
+                    lastSettlementPrice = requestedPrice > oraclePrice
+                        ? requestedPrice - oraclePrice
+                        : oraclePrice - requestedPrice;
+                }
+            }
+        "#;
+        let options = TranspileOptions { emit_comments: false, ..Default::default() };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains(concat!(
+            "  (begin\n",
+            "    (asserts! (> requested-price u0) (err ERR_ZERO_PRICE))\n",
+            "    (var-set last-settlement-price\n",
+            "      (if (> requested-price oracle-price)\n",
+            "        (- requested-price oracle-price)\n",
+            "        (- oracle-price requested-price)))\n",
+            "    (ok true)))\n",
+        )));
+
+        let options = TranspileOptions { emit_comments: false, indent: 4, ..Default::default() };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains(concat!(
+            "    (begin\n",
+            "        (asserts! (> requested-price u0) (err ERR_ZERO_PRICE))\n",
+            "        (var-set last-settlement-price\n",
+            "            (if (> requested-price oracle-price)\n",
+            "                (- requested-price oracle-price)\n",
+            "                (- oracle-price requested-price)))\n",
+            "        (ok true)))\n",
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_typed_variables_are_uint() -> Result<()> {
+        let source = r#"
+            contract Auction {
+                enum State { Pending, Active, Closed }
+                State public status;
+                function setStatus(State next) public {
+                    status = next;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.enums[0].members, vec!["Pending", "Active", "Closed"]);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        assert!(clarity_contract.warnings.is_empty());
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            ";; @enum State: u0 = Pending, u1 = Active, u2 = Closed\n(define-data-var status uint u0)"
+        ));
+        assert!(clarity_code.contains("(define-public (set-status (next uint))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_member_comparison() -> Result<()> {
+        let source = r#"
+            contract Auction {
+                enum State { Pending, Active, Closed }
+                State public status;
+                function bid() public {
+                    require(status == State.Active, "not active");
+                    status = State.Closed;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-constant STATE_ACTIVE u1)"));
+        assert!(clarity_code.contains("(define-constant STATE_CLOSED u2)"));
+        assert!(clarity_code.contains("(asserts! (is-eq (var-get status) STATE_ACTIVE) (err ERR_NOT_ACTIVE))"));
+        assert!(clarity_code.contains("(var-set status STATE_CLOSED)"));
+
+        let source = r#"
+            contract Auction {
+                enum State { Pending, Active }
+                State status;
+                function done() public view returns (bool) {
+                    return status == State.Closed;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err();
+        assert!(err.to_string().contains("Unknown member Closed of enum State"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mapping_to_array_is_a_list_valued_map() -> Result<()> {
+        let source = r#"
+            contract Owned {
+                mapping(address => uint256[]) ownedTokens;
+                mapping(uint256 => address[5]) slots;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.state_variables[0].mapping_value_type.as_deref(), Some("uint256[]"));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-map owned-tokens principal (list 256 uint))"));
+        assert!(clarity_code.contains("(ok (default-to (list) (map-get? owned-tokens key))))"));
+        assert!(clarity_code.contains("(define-map slots uint (list 5 principal))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_selftest_transpiles_the_bundled_example() -> Result<()> {
+        let clarity_code = crate::selftest()?;
+        assert!(clarity_code.contains("(define-public (increment)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_constant_array_is_a_list() -> Result<()> {
+        let source = r#"
+            contract Weights {
+                uint256[3] constant WEIGHTS = [1, 2, 3];
+                int256[2] offsets = [-1, 2];
+                function defaults() public pure returns (uint256[3] memory) {
+                    uint256[3] memory values = [uint256(4), 5, 6];
+                    return values;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert!(matches!(contract.state_variables[0].initial_value, Some(Expression::ArrayLiteral(ref items)) if items.len() == 3));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(define-constant WEIGHTS (list u1 u2 u3))"));
+        assert!(clarity_code.contains("(define-data-var offsets (list 2 int) (list -1 2))"));
+        assert!(clarity_code.contains("(let ((values (list u4 u5 u6))) values)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_zero_comparison_is_a_membership_check_when_enabled() -> Result<()> {
+        let source = r#"
+            contract Registry {
+                mapping(address => uint256) balances;
+                mapping(uint256 => address) owners;
+                function withdraw() public {
+                    require(balances[msg.sender] != 0, "no balance");
+                    delete balances[msg.sender];
+                }
+                function isFree(uint256 id) public view returns (bool) {
+                    return address(0) == owners[id];
+                }
+            }
+        "#;
+        // By default the stored value is compared, so an entry set to zero
+        // reads as empty
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        assert!(clarity_contract.warnings.is_empty());
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains(
+            "(asserts! (not (is-eq (default-to u0 (map-get? balances tx-sender)) u0)) (err ERR_NO_BALANCE))"
+        ));
+        assert!(!clarity_code.contains("is-some"));
+
+        let options = TranspileOptions {
+            membership_checks: true,
+            ..Default::default()
+        };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_contract = convert_contract(contract, &options)?;
+        assert_eq!(clarity_contract.warnings.len(), 2);
+        assert_eq!(clarity_contract.warnings[0].severity, crate::Severity::Note);
+        let clarity_code = generator::generate(clarity_contract, &options)?;
+        assert!(clarity_code.contains("(asserts! (is-some (map-get? balances tx-sender)) (err ERR_NO_BALANCE))"));
+        assert!(clarity_code.contains("(is-none (map-get? owners "));
+        assert!(!clarity_code.contains("ZERO_ADDRESS"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_getters_return_response_toggle() -> Result<()> {
+        let source = r#"
+            contract Token {
+                uint256 public constant LIMIT = 10;
+                uint256 public totalSupply;
+                mapping(address => uint256) balances;
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-read-only (get-limit)\n  (ok LIMIT))"));
+        assert!(clarity_code.contains("(define-read-only (get-total-supply)\n  (ok (var-get total-supply)))"));
+        assert!(clarity_code.contains("  (ok (default-to u0 (map-get? balances key))))"));
+
+        let options = TranspileOptions { getters_return_response: false, ..Default::default() };
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains("(define-read-only (get-limit)\n  LIMIT)"));
+        assert!(clarity_code.contains("(define-read-only (get-total-supply)\n  (var-get total-supply))"));
+        assert!(clarity_code.contains("  (default-to u0 (map-get? balances key)))"));
+        assert!(!clarity_code.contains("(ok "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_using_for_library_calls() -> Result<()> {
+        let source = r#"
+            contract Vault {
+                using SafeMath for uint256;
+                mapping(address => uint256) balances;
+                function sum() public pure returns (uint256) {
+                    uint256 a = 1;
+                    uint256 b = 2;
+                    return a.add(b);
+                }
+                function credit() public {
+                    balances[msg.sender] = balances[msg.sender].add(5).mul(2);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        assert_eq!(contract.using.len(), 1);
+        assert_eq!(contract.using[0].library, "SafeMath");
+        assert_eq!(contract.using[0].target.as_deref(), Some("uint256"));
+        let clarity_contract = convert_contract(contract, &Default::default())?;
+        let clarity_code = generator::generate(clarity_contract, &Default::default())?;
+        assert!(clarity_code.contains("(let ((a u1) (b u2)) (+ a b))"));
+        assert!(clarity_code.contains(
+            "(map-set balances tx-sender (* (+ (default-to u0 (map-get? balances tx-sender)) u5) u2))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_library_functions_are_inlined() -> Result<()> {
+        let source = r#"
+            library Math {
+                function square(uint256 a) internal pure returns (uint256) {
+                    return a * a;
+                }
+                function cube(uint256 a) internal pure returns (uint256) {
+                    return square(a) * a;
+                }
+            }
+            contract Calc {
+                using Math for uint256;
+                uint256 total;
+                function grow(uint256 x) public {
+                    total = x.cube();
+                }
+            }
+        "#;
+        let contracts = parser::parse_all(source)?;
+        assert!(contracts[0].is_library);
+        let clarity_contracts = crate::transpiler::convert(contracts, &Default::default())?;
+        // The library itself is not deployed
+        assert_eq!(clarity_contracts.len(), 1);
+        let clarity_code = generator::generate(clarity_contracts.into_iter().next().unwrap(), &Default::default())?;
+        assert!(clarity_code.contains("(var-set total (try! (cube x)))"));
+        assert!(clarity_code.contains("(define-private (cube (a uint))"));
+        assert!(clarity_code.contains("(ok (* (try! (square a)) a))"));
+        assert!(!clarity_code.contains("contract-call?"));
+
+        let source = r#"
+            contract Vault {
+                using Strings for uint256;
+                function label() public pure returns (uint256) {
+                    uint256 id = 7;
+                    return id.toHexString();
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        assert!(err.contains("Strings.toHexString in label has no Clarity builtin"), "unexpected error: {}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_converter_errors_point_at_the_source() -> Result<()> {
+        let source = r#"
+contract Machine {
+    enum State { Open, Closed }
+    State state;
+
+    function close() public {
+        state = State.Closed;
+        {
+            uint256 attempts = 1;
+            state = State.Shut;
+        }
+    }
+}
+"#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        // The statement holding the failing expression, not the function
+        assert!(err.contains("error at line 10:13: Unknown member Shut of enum State"), "unexpected error: {}", err);
+        assert!(err.contains("\n  |             state = State.Shut;\n  |             ^"), "unexpected error: {}", err);
+
+        let source = r#"
+contract Limits {
+    mapping(uint256[] => uint256) limits;
+}
+"#;
+        let err = parser::parse_all(source).unwrap_err().to_string();
+        assert!(err.starts_with("error at line 3:13: Invalid mapping key type uint256[]"), "unexpected error: {}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_nested_mapping_entry() -> Result<()> {
+        let source = r#"
+            contract Approvals {
+                mapping(address => mapping(uint256 => bool)) approvals;
+                function revoke() public {
+                    uint256 tokenId = 7;
+                    delete approvals[msg.sender][tokenId];
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-map approvals {owner: principal, token-id: uint} bool)"));
+        assert!(clarity_code.contains("(map-delete approvals {owner: tx-sender, token-id: token-id})"));
+
+        let source = r#"
+            contract Approvals {
+                mapping(address => mapping(uint256 => bool)) approvals;
+                function revokeAll() public {
+                    delete approvals[msg.sender];
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let err = convert_contract(contract, &Default::default()).unwrap_err().to_string();
+        assert!(err.contains("can only delete single entries, keyed by all 2 levels"), "unexpected error: {}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_envelope() -> Result<()> {
+        let source = r#"
+            contract Token {
+                event Transfer(address from, address to, uint256 amount);
+                event Paused();
+                function send() public {
+                    emit Transfer(msg.sender, msg.sender, 5);
+                    emit Paused();
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract.clone(), &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(print {event: \"Transfer\", from: tx-sender, to: tx-sender, amount: u5})"));
+
+        let options = TranspileOptions { event_envelope: true, ..Default::default() };
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains(
+            "(print {topic: \"event\", name: \"Transfer\", data: {from: tx-sender, to: tx-sender, amount: u5}})"
+        ));
+        assert!(clarity_code.contains("(print {topic: \"event\", name: \"Paused\"})"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parameter_shadowing_state_variable() -> Result<()> {
+        let source = r#"
+            contract Counter {
+                uint256 count;
+                uint256 last;
+                function setCount(uint256 count) public {
+                    last = count;
+                    count = count + 1;
+                    last = last + count;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-public (set-count (count-param uint))"));
+        assert!(clarity_code.contains("(var-set last count-param)"));
+        // Assigning to the parameter rebinds it; the state variable is untouched
+        assert!(clarity_code.contains("(let ((count-1 (+ count-param u1))) (var-set last (+ (var-get last) count-1)))"));
+        assert!(!clarity_code.contains("(var-set count"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_identifier_reads_by_kind() -> Result<()> {
+        let source = r#"
+            contract Capped {
+                uint256 constant MAX_SUPPLY = 1000;
+                uint256 constant maxPerTx = 10;
+                uint256 supply;
+                function mint(uint256 amount) public {
+                    uint256 next = supply + amount;
+                    require(amount <= maxPerTx, "too many");
+                    require(next <= MAX_SUPPLY, "cap");
+                    supply = next;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        // Constants are read by name, state variables with var-get, and
+        // parameters and locals by name
+        assert!(clarity_code.contains("(asserts! (<= amount max-per-tx) (err ERR_TOO_MANY))"));
+        assert!(clarity_code.contains("(let ((next (+ (var-get supply) amount)))"));
+        assert!(clarity_code.contains("(asserts! (<= next MAX_SUPPLY) (err ERR_CAP))"));
+        assert!(!clarity_code.contains("(var-get MAX_SUPPLY)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_returned_map_lookup_defaults_missing_entries() -> Result<()> {
+        let source = r#"
+            contract Bank {
+                mapping(address => uint256) balances;
+                mapping(address => bool) frozen;
+                function balanceOf(address addr) public returns (uint256) {
+                    return balances[addr];
+                }
+                function status(address addr) public view returns (uint256 balance, bool isFrozen) {
+                    return (balances[addr], frozen[addr]);
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(define-public (balance-of (addr principal))\n  (ok (default-to u0 (map-get? balances addr))))"));
+        assert!(clarity_code.contains(
+            "(ok {balance: (default-to u0 (map-get? balances addr)),\n       is-frozen: (default-to false (map-get? frozen addr))}))"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_payable_cast_is_dropped() -> Result<()> {
+        let transpile = |call: &str| -> Result<String> {
+            let source = format!(r#"
+                contract Vault {{
+                    function pay(address recipient, uint256 amount) public {{
+                        {};
+                    }}
+                }}
+            "#, call);
+            let contract = parser::parse_all(&source)?.remove(0);
+            generator::generate(convert_contract(contract, &Default::default())?, &Default::default())
+        };
+        let cast = transpile("payable(recipient).transfer(amount)")?;
+        assert_eq!(cast, transpile("recipient.transfer(amount)")?);
+        assert_eq!(transpile("address to = payable(recipient)")?, transpile("address to = recipient")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_preserve_map_optionals_toggle() -> Result<()> {
+        let source = r#"
+            contract Bank {
+                mapping(address => uint256) balances;
+                function balanceOf(address addr) public view returns (uint256) {
+                    return balances[addr];
+                }
+                function doubled(address addr) public view returns (uint256) {
+                    return balances[addr] * 2;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract.clone(), &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains(";; @returns (response uint uint)\n(define-read-only (balance-of (addr principal))"));
+        assert!(clarity_code.contains("(ok (default-to u0 (map-get? balances addr)))"));
+
+        let options = TranspileOptions { preserve_map_optionals: true, ..Default::default() };
+        let clarity_code = generator::generate(convert_contract(contract, &options)?, &options)?;
+        assert!(clarity_code.contains(";; @returns (response (optional uint) uint)\n(define-read-only (balance-of (addr principal))"));
+        assert!(clarity_code.contains("(ok (map-get? balances addr))"));
+        // Only the entry itself is returned as an optional
+        assert!(clarity_code.contains("(ok (* (default-to u0 (map-get? balances addr)) u2))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_named_return_keeps_value_from_before_later_writes() -> Result<()> {
+        let source = r#"
+            contract Counter {
+                uint256 count;
+                function reset() public returns (uint256 previous) {
+                    previous = count;
+                    count = 0;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        // The old count is bound before the reset, not read again at return
+        assert!(clarity_code.contains("(let ((previous (var-get count))) (var-set count u0) previous)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reassigned_parameter_keeps_value_from_before_later_writes() -> Result<()> {
+        let source = r#"
+            contract Counter {
+                uint256 count;
+                function bump(uint256 x) public returns (uint256) {
+                    x = count;
+                    count += 1;
+                    return x;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        assert!(clarity_code.contains("(let ((x-1 (var-get count))) (var-set count (+ (var-get count) u1)) x-1)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_internal_calls_unwrap_the_callee_response() -> Result<()> {
+        let source = r#"
+            contract Math {
+                uint256 total;
+                function double(uint256 x) private pure returns (uint256) {
+                    return x * 2;
+                }
+                function bump() private {
+                    total += 1;
+                }
+                function run(uint256 x) public returns (uint256) {
+                    bump();
+                    return double(x) + 1;
+                }
+            }
+        "#;
+        let contract = parser::parse_all(source)?.remove(0);
+        let clarity_code = generator::generate(convert_contract(contract, &Default::default())?, &Default::default())?;
+        // Every function returns a response, so callers take the value out
+        assert!(clarity_code.contains("(define-private (double (x uint))\n  (ok (* x u2)))"));
+        assert!(clarity_code.contains("(try! (bump))"));
+        assert!(clarity_code.contains("(ok (+ (try! (double x)) u1))"));
+        Ok(())
+    }
+}
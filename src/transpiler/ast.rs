@@ -1,36 +1,129 @@
-#[derive(Debug)]
+use super::warning::Warning;
+
+/// A parsed Solidity file: its imports, version pragma and the contracts
+/// it declares.
+#[derive(Debug, Clone)]
+pub struct SourceUnit {
+    pub imports: Vec<String>,
+    /// Version requirement from `pragma solidity ...;`, e.g. `^0.8.0`.
+    pub pragma: Option<String>,
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Contract {
     pub name: String,
     pub functions: Vec<Function>,
     pub state_variables: Vec<StateVariable>,
     pub events: Vec<Event>,
     pub constructor: Option<Constructor>,
+    pub base_contracts: Vec<String>,
+    /// Declared with `interface`; functions carry signatures only.
+    pub is_interface: bool,
+    /// Declared with `library`; not deployed, its functions are inlined
+    /// into the contracts that bind it with `using`.
+    pub is_library: bool,
+    pub structs: Vec<Struct>,
+    pub enums: Vec<EnumDef>,
+    pub using: Vec<UsingDirective>,
+    pub errors: Vec<ErrorDef>,
+    /// Members the transpiler recognizes but cannot convert.
+    pub unsupported: Vec<UnsupportedNote>,
+    /// Problems found while resolving its bases.
+    pub warnings: Vec<Warning>,
+}
+
+/// Where a declaration starts in its source file, so that errors about it
+/// can point at the original Solidity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// 1-based line.
+    pub line: usize,
+    /// 1-based column.
+    pub column: usize,
+    /// The text of the whole line, shown under the message.
+    pub source_line: String,
+}
+
+impl Span {
+    /// Renders `message` as `error at line L:C: <message>` followed by the
+    /// source line and a caret under the column.
+    pub fn diagnostic(&self, message: &str) -> String {
+        format!(
+            "error at line {}:{}: {}\n  | {}\n  | {}^",
+            self.line,
+            self.column,
+            message,
+            self.source_line.trim_end(),
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
 }
 
-#[derive(Debug)]
+/// A Solidity construct that is skipped rather than converted.
+#[derive(Debug, Clone)]
+pub struct UnsupportedNote {
+    /// What was skipped, e.g. `inline assembly`.
+    pub construct: String,
+    /// 1-based line of the construct in its source file.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<Parameter>,
+}
+
+/// `enum State { Pending, Active }`; members are numbered from 0 in order.
+#[derive(Debug, Clone)]
+pub struct EnumDef {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+/// `using Library for Type;`, which lets values of `Type` call the
+/// library's functions as methods.
+#[derive(Debug, Clone)]
+pub struct UsingDirective {
+    pub library: String,
+    /// The bound type, or `None` for `using Library for *`.
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Function {
     pub name: String,
     pub params: Vec<Parameter>,
-    pub return_type: Option<String>,
+    /// Declared return values as `(name, type)`; names are optional.
+    pub return_type: Vec<(Option<String>, String)>,
     pub visibility: Option<String>,
     pub mutability: Option<String>,
     pub body: Vec<Statement>,
+    /// `false` for a declaration without a body, as in an interface.
+    pub implemented: bool,
+    /// The library it was inlined from, if any.
+    pub library: Option<String>,
+    pub span: Option<Span>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Constructor {
     pub params: Vec<Parameter>,
     pub visibility: Option<String>,
+    /// Base constructors invoked in the header, with their arguments.
+    pub base_calls: Vec<(String, Vec<Expression>)>,
     pub body: Vec<Statement>,
+    pub span: Option<Span>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Parameter {
     pub name: String,
     pub param_type: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StateVariable {
     pub name: String,
     pub var_type: String,
@@ -39,44 +132,92 @@ pub struct StateVariable {
     pub mapping_key_type: Option<String>,
     pub mapping_value_type: Option<String>,
     pub is_constant: bool,
+    /// Declared `immutable`: assigned once, in the constructor.
+    pub is_immutable: bool,
     pub initial_value: Option<Expression>,
     pub nested_mapping: Option<Box<MappingType>>,
+    pub span: Option<Span>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MappingType {
     pub key_type: String,
     pub value_type: String,
     pub nested: Option<Box<MappingType>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Event {
     pub name: String,
     pub params: Vec<EventParameter>,
 }
 
-#[derive(Debug)]
+/// A Solidity 0.8 custom error, `error Unauthorized(address caller);`.
+#[derive(Debug, Clone)]
+pub struct ErrorDef {
+    pub name: String,
+    pub params: Vec<Parameter>,
+}
+
+#[derive(Debug, Clone)]
 pub struct EventParameter {
     pub name: String,
     pub param_type: String,
     pub indexed: bool,
 }
 
-#[derive(Debug)]
-pub enum Statement {
+/// A statement and where it starts, so that errors converting it, or any
+/// expression in it, can point at it.
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    /// `None` for statements the transpiler synthesizes, which are located
+    /// by the declaration or statement they belong to.
+    pub span: Option<Span>,
+}
+
+impl From<StatementKind> for Statement {
+    fn from(kind: StatementKind) -> Self {
+        Statement { kind, span: None }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StatementKind {
     Expression(Expression),
-    Return(Expression),
+    /// `return expr;`, or a bare `return;` when `None`.
+    Return(Option<Expression>),
     Assignment(String, Expression),
     MapAccessAssignment(String, Box<Expression>, Expression),
+    /// `map[key].field = value` on a struct-valued mapping.
+    FieldAssignment(String, Box<Expression>, String, Expression),
     Emit(String, Vec<Expression>),
+    While(Expression, Vec<Statement>),
+    /// A nested `{ ... }` scope.
+    Block(Vec<Statement>),
+    Delete(Expression),
+    /// `revert Error(args)` with the custom error name, or a plain `revert(...)`.
+    Revert(Option<String>, Vec<Expression>),
+    /// `T name = value;` declared inside a function body, as `(name, T, value)`.
+    LocalVar(String, String, Option<Expression>),
+    /// `assert(cond)`: an invariant, as opposed to `require`'s input checks.
+    Assert(Expression),
+    Unsupported(UnsupportedNote),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Expression {
     Literal(String),
     Identifier(String),
     BinaryOp(Box<Expression>, String, Box<Expression>),
     MapAccess(String, Box<Expression>),
     MemberAccess(Box<Expression>, String),
+    Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
+    UnaryOp(String, Box<Expression>),
+    FunctionCall(Box<Expression>, Vec<Expression>),
+    Tuple(Vec<Expression>),
+    /// An inline array, `[1, 2, 3]`.
+    ArrayLiteral(Vec<Expression>),
+    /// Elementary type conversion such as `uint8(x)` or `address(y)`.
+    Cast(String, Box<Expression>),
 }
\ No newline at end of file
@@ -1,5 +1,11 @@
 use super::ast::*;
-use anyhow::Result;
+use super::optimize::hoist_repeated_reads;
+use super::options::{ClarityVersion, NamingStyle, ReservedNamePolicy, TranspileOptions};
+use super::types::{normalize_type, ClarityType};
+use super::warning::{Severity, Warning};
+use crate::generator::{apply_naming, to_kebab_case, trait_name};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct ClarityContract {
@@ -8,6 +14,25 @@ pub struct ClarityContract {
     pub data_vars: Vec<ClarityDataVar>,
     pub maps: Vec<ClarityMap>,
     pub events: Vec<ClarityEvent>,
+    /// Generated as a `define-trait` rather than a contract.
+    pub is_interface: bool,
+    /// Solidity version pragma of the source file, for the header.
+    pub pragma: Option<String>,
+    /// Allocated error codes and the message or error name each stands for.
+    pub error_codes: Vec<(u32, String)>,
+    /// Traits of the interfaces its functions take as parameters.
+    pub traits: Vec<TraitRef>,
+    /// Skipped constructs and lossy conversions, in the order found.
+    pub warnings: Vec<Warning>,
+}
+
+/// A `use-trait` import for an interface a parameter is typed with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitRef {
+    /// Local name of the trait, e.g. `token-trait`.
+    pub alias: String,
+    /// Contract defining the trait, e.g. `.itoken`.
+    pub contract: String,
 }
 
 #[derive(Debug)]
@@ -16,29 +41,38 @@ pub struct ClarityFunction {
     pub params: Vec<ClarityParameter>,
     pub public: bool,
     pub read_only: bool,
+    /// Clarity type of the value returned inside the response, if declared.
+    pub return_type: Option<String>,
+    /// Whether the final body expression is wrapped in `(ok ...)`.
+    pub wraps_response: bool,
     pub body: Vec<ClarityExpression>,
+    /// Warnings about the conversion, emitted as comments above the function.
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct ClarityParameter {
     pub name: String,
-    pub param_type: String,
+    pub param_type: ClarityType,
 }
 
 #[derive(Debug)]
 pub struct ClarityDataVar {
     pub name: String,
-    pub var_type: String,
+    pub var_type: ClarityType,
     pub initial_value: String,
     pub is_constant: bool,
     pub visibility: Option<String>,
+    /// For an enum-typed variable, the enum's name and its members in
+    /// numbering order.
+    pub enum_members: Option<(String, Vec<String>)>,
 }
 
 #[derive(Debug)]
 pub struct ClarityMap {
     pub name: String,
-    pub key_type: String,
-    pub value_type: String,
+    pub key_type: ClarityType,
+    pub value_type: ClarityType,
 }
 
 #[derive(Debug)]
@@ -54,7 +88,7 @@ pub struct ClarityEventField {
     pub indexed: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ClarityExpression {
     Literal(String),
     Var(String),
@@ -62,255 +96,2136 @@ pub enum ClarityExpression {
     MapGet(String, Vec<ClarityExpression>),
     MapSet(String, Vec<ClarityExpression>, Box<ClarityExpression>),
     Print(Vec<ClarityExpression>),
+    Tuple(Vec<(String, ClarityExpression)>),
+    /// `(let ((name value) ...) body...)`
+    Let(Vec<(String, ClarityExpression)>, Vec<ClarityExpression>),
 }
 
-fn convert_nested_mapping_type(mapping: &MappingType) -> (String, String) {
-    if let Some(nested) = &mapping.nested {
-        let (nested_key_type, nested_value_type) = convert_nested_mapping_type(nested);
-        (
-            format!("{{owner: {}, token-id: {}}}", 
-                convert_solidity_type(&mapping.key_type),
-                nested_key_type
-            ),
-            nested_value_type
-        )
-    } else {
-        (
-            convert_solidity_type(&mapping.key_type),
-            convert_solidity_type(&mapping.value_type)
-        )
-    }
-}
-
-pub fn convert_solidity_type(solidity_type: &str) -> String {
-    match solidity_type {
-        "uint256" | "uint" => "uint".to_string(),
-        "bool" => "bool".to_string(),
-        "address" => "principal".to_string(),
-        "string" => "string-ascii".to_string(),
-        _ => {
-            if solidity_type.starts_with("mapping") {
-                solidity_type.to_string()
+/// Flattens a chain of nested mappings into a single tuple key type holding
+/// one field per level, and the innermost value type.
+fn convert_nested_mapping_type(mapping: &MappingType, ctx: &ConversionContext) -> (ClarityType, ClarityType) {
+    let mut key_types = vec![ctx.convert_type(&mapping.key_type)];
+    let mut innermost = mapping;
+    while let Some(nested) = &innermost.nested {
+        key_types.push(ctx.convert_type(&nested.key_type));
+        innermost = nested;
+    }
+    let value_type = ctx.convert_type(&innermost.value_type);
+    if key_types.len() == 1 {
+        return (key_types.remove(0), value_type);
+    }
+    let fields = nested_key_names(key_types.len()).into_iter()
+        .zip(key_types)
+        .collect();
+    (ClarityType::Tuple(fields), value_type)
+}
+
+/// Field names of the tuple key of a flattened nested mapping. Two levels
+/// use the conventional `owner`/`token-id`; deeper ones are numbered.
+fn nested_key_names(depth: usize) -> Vec<String> {
+    if depth == 2 {
+        return vec!["owner".to_string(), "token-id".to_string()];
+    }
+    (1..=depth).map(|i| format!("key-{}", i)).collect()
+}
+
+/// Splits the `a, b, c` chain the parser builds for `m[a][b][c]` into its keys.
+fn flatten_map_keys(expr: Expression, keys: &mut Vec<Expression>) {
+    match expr {
+        Expression::BinaryOp(left, op, right) if op == "," => {
+            flatten_map_keys(*left, keys);
+            flatten_map_keys(*right, keys);
+        }
+        other => keys.push(other),
+    }
+}
+
+/// Converts a Solidity type name to the Clarity type it is declared as.
+/// Unknown and malformed names become `uint`; `check_declared_types`
+/// reports them.
+pub fn convert_solidity_type(solidity_type: &str, options: &TranspileOptions) -> ClarityType {
+    match normalize_type(solidity_type, options) {
+        Ok(clarity_type) => unknown_as_uint(clarity_type),
+        Err(_) => ClarityType::Uint,
+    }
+}
+
+fn unknown_as_uint(clarity_type: ClarityType) -> ClarityType {
+    match clarity_type {
+        ClarityType::Unknown(_) => ClarityType::Uint,
+        ClarityType::List(size, element) => ClarityType::List(size, Box::new(unknown_as_uint(*element))),
+        other => other,
+    }
+}
+
+/// The name a type is declared with, stripped of array suffixes.
+fn base_type_name(clarity_type: &ClarityType) -> Option<&str> {
+    match clarity_type {
+        ClarityType::Unknown(name) => Some(name),
+        ClarityType::List(_, element) => base_type_name(element),
+        _ => None,
+    }
+}
+
+/// Reports declared types that do not convert: malformed elementary types
+/// are errors, and names that are neither elementary nor a struct or enum
+/// are errors in strict mode and warnings otherwise.
+fn check_declared_types(contract: &Contract, ctx: &mut ConversionContext) {
+    let mut declared: Vec<&str> = Vec::new();
+    for var in &contract.state_variables {
+        declared.push(&var.var_type);
+        declared.extend(var.mapping_value_type.as_deref());
+        let mut nested = var.nested_mapping.as_deref();
+        while let Some(mapping) = nested {
+            declared.push(&mapping.value_type);
+            nested = mapping.nested.as_deref();
+        }
+    }
+    for func in &contract.functions {
+        declared.extend(func.params.iter().map(|p| p.param_type.as_str()));
+        declared.extend(func.return_type.iter().map(|(_, t)| t.as_str()));
+    }
+    if let Some(constructor) = &contract.constructor {
+        declared.extend(constructor.params.iter().map(|p| p.param_type.as_str()));
+    }
+    for event in &contract.events {
+        declared.extend(event.params.iter().map(|p| p.param_type.as_str()));
+    }
+    for item in &contract.structs {
+        declared.extend(item.fields.iter().map(|f| f.param_type.as_str()));
+    }
+
+    let mut reported = Vec::new();
+    for solidity_type in declared {
+        if solidity_type.starts_with("mapping") || reported.contains(&solidity_type) {
+            continue;
+        }
+        let unknown = match normalize_type(solidity_type, ctx.options) {
+            Ok(clarity_type) => base_type_name(&clarity_type)
+                .filter(|name| {
+                    !ctx.structs.contains_key(*name) && !ctx.enums.contains_key(*name)
+                        && !ctx.peers.iter().any(|p| p == name) && !ctx.interfaces.iter().any(|i| i == name)
+                })
+                .map(str::to_string),
+            Err(e) => {
+                ctx.errors.push(e.to_string());
+                None
+            }
+        };
+        if let Some(name) = unknown {
+            if ctx.options.strict {
+                ctx.errors.push(format!("Unknown type {}", name));
             } else {
-                "uint".to_string()
+                ctx.warnings.push(Warning {
+                    severity: Severity::Warning,
+                    message: format!("unknown type {} in {} was converted to uint", name, contract.name),
+                    line: None,
+                });
             }
         }
+        reported.push(solidity_type);
     }
 }
 
-fn convert_mapping(var: &StateVariable) -> Result<ClarityMap> {
+fn convert_mapping(var: &StateVariable, ctx: &ConversionContext) -> Result<ClarityMap> {
+    let key_type = var.mapping_key_type.clone()
+        .ok_or_else(|| anyhow!("Mapping {} has no key type", var.name))?;
+    let mut key_types = vec![key_type.as_str()];
+    let mut nested = var.nested_mapping.as_deref();
+    while let Some(mapping) = nested {
+        key_types.push(&mapping.key_type);
+        nested = mapping.nested.as_deref();
+    }
+    for key_type in key_types {
+        check_map_key_type(key_type, &var.name, ctx)?;
+    }
+
     if let Some(nested) = &var.nested_mapping {
-        let (key_type, value_type) = convert_nested_mapping_type(nested);
+        let outer = MappingType {
+            key_type,
+            value_type: nested.value_type.clone(),
+            nested: Some(nested.clone()),
+        };
+        let (key_type, value_type) = convert_nested_mapping_type(&outer, ctx);
         Ok(ClarityMap {
-            name: var.name.clone(),
+            name: declared_name(&var.name, VALUE_SUFFIX, ctx.options)?,
             key_type,
             value_type,
         })
     } else {
         Ok(ClarityMap {
-            name: var.name.clone(),
-            key_type: convert_solidity_type(&var.mapping_key_type.clone().unwrap()),
-            value_type: convert_solidity_type(&var.mapping_value_type.clone().unwrap()),
+            name: declared_name(&var.name, VALUE_SUFFIX, ctx.options)?,
+            key_type: ctx.convert_type(&key_type),
+            value_type: ctx.convert_type(var.mapping_value_type.as_deref().unwrap_or_default()),
         })
     }
 }
 
-pub fn convert_contract(contract: Contract) -> Result<ClarityContract> {
+/// Rejects mapping keys that would otherwise fall back to `uint`: only
+/// elementary types and structs of them can be represented as Clarity keys.
+fn check_map_key_type(key_type: &str, map_name: &str, ctx: &ConversionContext) -> Result<()> {
+    let elementary = matches!(key_type, "address" | "bool" | "string" | "bytes")
+        || ["uint", "int", "bytes"].iter().any(|prefix| {
+            key_type.strip_prefix(prefix).is_some_and(|bits| bits.chars().all(|c| c.is_ascii_digit()))
+        });
+    let representable = elementary
+        || ctx.structs.get(key_type).is_some_and(is_comparable_type);
+    if representable {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Unsupported key type {} for mapping {}: Clarity map keys must be principal, uint, int, bool, buff, string or tuples of those",
+            key_type, map_name
+        ))
+    }
+}
+
+fn is_comparable_type(clarity_type: &ClarityType) -> bool {
+    match clarity_type {
+        ClarityType::Uint | ClarityType::Int | ClarityType::Bool | ClarityType::Principal
+        | ClarityType::Buff(_) | ClarityType::StringAscii(_) | ClarityType::StringUtf8(_) => true,
+        ClarityType::Tuple(fields) => fields.iter().all(|(_, field_type)| is_comparable_type(field_type)),
+        _ => false,
+    }
+}
+
+/// Names that occur more than once, in order of their second occurrence.
+fn duplicate_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = Vec::new();
+    let mut duplicates = Vec::new();
+    for name in names {
+        if seen.contains(&name) {
+            if !duplicates.contains(&name) {
+                duplicates.push(name);
+            }
+        } else {
+            seen.push(name);
+        }
+    }
+    duplicates
+}
+
+pub fn convert_contract(contract: Contract, options: &TranspileOptions) -> Result<ClarityContract> {
+    convert_contract_with_peers(contract, &[], &[], options)
+}
+
+/// Converts `contract`, resolving calls to the contracts named in `peers`
+/// and parameters typed with the `interfaces`, which are deployed alongside
+/// it.
+pub fn convert_contract_with_peers(
+    contract: Contract,
+    peers: &[String],
+    interfaces: &[String],
+    options: &TranspileOptions,
+) -> Result<ClarityContract> {
+    let duplicate_vars = duplicate_names(contract.state_variables.iter().map(|v| v.name.as_str()));
+    if !duplicate_vars.is_empty() {
+        return Err(anyhow!(
+            "Duplicate state variable names in {}: {}",
+            contract.name, duplicate_vars.join(", ")
+        ));
+    }
+    let duplicate_functions = duplicate_names(contract.functions.iter().map(|f| f.name.as_str()));
+    if !duplicate_functions.is_empty() {
+        return Err(anyhow!(
+            "Duplicate function names in {}: {}",
+            contract.name, duplicate_functions.join(", ")
+        ));
+    }
+
     let mut clarity_contract = ClarityContract {
-        name: contract.name,
+        name: contract.name.clone(),
         functions: Vec::new(),
         data_vars: Vec::new(),
         maps: Vec::new(),
         events: Vec::new(),
+        is_interface: contract.is_interface,
+        pragma: None,
+        error_codes: Vec::new(),
+        traits: Vec::new(),
+        warnings: Vec::new(),
     };
 
+    let mut ctx = ConversionContext::new(options, &contract.events);
+    for item in &contract.structs {
+        let tuple_type = ClarityType::Tuple(item.fields.iter()
+            .map(|f| (apply_naming(&f.name, options.naming), ctx.convert_type(&f.param_type)))
+            .collect());
+        ctx.structs.insert(item.name.clone(), tuple_type);
+    }
+    for item in &contract.enums {
+        ctx.enums.insert(item.name.clone(), item.members.clone());
+    }
+    ctx.using = contract.using.clone();
+    ctx.peers = peers.iter().filter(|peer| **peer != contract.name).cloned().collect();
+    ctx.interfaces = interfaces.to_vec();
+    for var in &contract.state_variables {
+        ctx.state_types.insert(var.name.clone(), var.var_type.clone());
+        if !var.is_mapping {
+            let symbol = if var.is_constant { Symbol::Constant } else { Symbol::DataVar };
+            ctx.symbols.insert(var.name.clone(), symbol);
+        }
+    }
+    check_declared_types(&contract, &mut ctx);
+    ctx.warnings.extend(contract.warnings.iter().cloned());
+    for note in &contract.unsupported {
+        if options.strict {
+            ctx.errors.push(format!("line {}: {} is not supported", note.line, note.construct));
+        } else {
+            ctx.warnings.push(Warning {
+                severity: Severity::Warning,
+                message: format!("{} in {} is not supported and was skipped", note.construct, clarity_contract.name),
+                line: Some(note.line),
+            });
+        }
+    }
+    for error in &contract.errors {
+        let code = ctx.next_error_code(&error.name);
+        ctx.add_constant(&error_constant(&error.name), ClarityType::Uint, format!("u{}", code));
+    }
+
     for var in contract.state_variables {
+        ctx.span = var.span.clone();
         if var.is_mapping {
-            clarity_contract.maps.push(convert_mapping(&var)?);
+            clarity_contract.maps.push(convert_mapping(&var, &ctx).map_err(|e| locate(&ctx.span, e))?);
         } else {
-            clarity_contract.data_vars.push(convert_state_variable(var));
+            let is_immutable = var.is_immutable;
+            let data_var = convert_state_variable(var, &mut ctx).map_err(|e| locate(&ctx.span, e))?;
+            if is_immutable {
+                ctx.immutables.push(data_var.name.clone());
+            }
+            clarity_contract.data_vars.push(data_var);
         }
     }
 
-    for event in contract.events {
+    for event in &contract.events {
         clarity_contract.events.push(ClarityEvent {
-            name: event.name,
-            fields: event.params.into_iter()
+            name: event.name.clone(),
+            fields: event.params.iter()
                 .map(|p| ClarityEventField {
-                    name: p.name,
-                    field_type: convert_solidity_type(&p.param_type),
+                    name: p.name.clone(),
+                    field_type: convert_solidity_type(&p.param_type, options).to_string(),
                     indexed: p.indexed,
                 })
                 .collect(),
         });
     }
 
+    for var in &clarity_contract.data_vars {
+        ctx.var_types.insert(var.name.clone(), var.var_type.clone());
+    }
+    for map in &clarity_contract.maps {
+        ctx.map_types.insert(map.name.clone(), map.value_type.clone());
+    }
+
+    ctx.functions = contract.functions.iter().map(|func| func.name.clone()).collect();
+    ctx.library_functions = contract.functions.iter()
+        .filter_map(|func| Some((func.library.clone()?, func.name.clone())))
+        .collect();
     if let Some(constructor) = contract.constructor {
+        let mut body = Vec::new();
+        // Immutables would be rewritable through a second init call
+        if options.guard_init || !ctx.immutables.is_empty() {
+            add_init_guard(&mut clarity_contract, &mut body, &mut ctx);
+        }
+        ctx.enter_function("init");
+        ctx.span = constructor.span.clone();
+        ctx.declare_parameters(&constructor.params);
+        ctx.in_constructor = true;
+        body.extend(convert_statements(constructor.body, &mut ctx).map_err(|e| locate(&ctx.span, e))?);
+        ctx.in_constructor = false;
+        clarity_contract.functions.append(&mut ctx.helpers);
+
         clarity_contract.functions.push(ClarityFunction {
             name: "init".to_string(),
-            params: constructor.params.into_iter()
-                .map(|p| ClarityParameter {
-                    name: p.name,
-                    param_type: convert_solidity_type(&p.param_type),
-                })
-                .collect(),
+            params: convert_parameters(constructor.params, &mut ctx).map_err(|e| locate(&ctx.span, e))?,
             public: true,
             read_only: false,
-            body: convert_statements(constructor.body)?,
+            return_type: None,
+            wraps_response: true,
+            body,
+            notes: std::mem::take(&mut ctx.notes),
         });
     }
 
     for func in contract.functions {
-        clarity_contract.functions.push(convert_function(func)?);
+        ctx.span = func.span.clone();
+        let function = convert_function(func, &mut ctx).map_err(|e| locate(&ctx.span, e))?;
+        clarity_contract.functions.append(&mut ctx.helpers);
+        clarity_contract.functions.push(function);
+    }
+    clarity_contract.data_vars.append(&mut ctx.constants);
+    clarity_contract.error_codes.append(&mut ctx.error_codes);
+    clarity_contract.traits.append(&mut ctx.traits);
+    clarity_contract.warnings.append(&mut ctx.warnings);
+
+    if !ctx.errors.is_empty() {
+        return Err(anyhow!("Cannot convert {}: {}", clarity_contract.name, ctx.errors.join("\n")));
     }
 
     Ok(clarity_contract)
 }
 
-fn convert_state_variable(var: StateVariable) -> ClarityDataVar {
-    let var_type = convert_solidity_type(&var.var_type);
-    let initial_value = if let Some(expr) = var.initial_value {
-        match expr {
-            Expression::Literal(val) => {
-                if var_type == "uint" && val.chars().all(|c| c.is_digit(10)) {
-                    format!("u{}", val)
-                } else {
-                    val
-                }
-            },
-            _ => match var_type.as_str() {
-                "uint" => "u0".to_string(),
-                "bool" => "false".to_string(),
-                "principal" => "tx-sender".to_string(),
-                "string-ascii" => "\"\"".to_string(),
-                _ => "u0".to_string(),
-            }
-        }
-    } else {
-        match var_type.as_str() {
-            "uint" => "u0".to_string(),
-            "bool" => "false".to_string(),
-            "principal" => "tx-sender".to_string(),
-            "string-ascii" => "\"\"".to_string(),
-            _ => "u0".to_string(),
+/// Points `error` at the source of the declaration it came from, when the
+/// parser recorded one.
+fn locate(span: &Option<Span>, error: anyhow::Error) -> anyhow::Error {
+    match span {
+        Some(span) => anyhow!(span.diagnostic(&error.to_string())),
+        None => error,
+    }
+}
+
+/// Declares the `initialized` flag and prepends the statements that make
+/// `init` callable only once, like a Solidity constructor.
+fn add_init_guard(contract: &mut ClarityContract, body: &mut Vec<ClarityExpression>, ctx: &mut ConversionContext) {
+    let code = ctx.next_error_code("already initialized");
+    ctx.add_constant(
+        "ERR_ALREADY_INITIALIZED",
+        ClarityType::Response(Box::new(ClarityType::Bool), Box::new(ClarityType::Uint)),
+        format!("(err u{})", code)
+    );
+    contract.data_vars.push(ClarityDataVar {
+        name: "initialized".to_string(),
+        var_type: ClarityType::Bool,
+        initial_value: "false".to_string(),
+        is_constant: false,
+        visibility: None,
+        enum_members: None,
+    });
+
+    let initialized = || ClarityExpression::Var("initialized".to_string());
+    body.push(ClarityExpression::FunctionCall(
+        "asserts!".to_string(),
+        vec![
+            ClarityExpression::FunctionCall(
+                "not".to_string(),
+                vec![ClarityExpression::FunctionCall("var-get".to_string(), vec![initialized()])]
+            ),
+            ClarityExpression::Var("ERR_ALREADY_INITIALIZED".to_string()),
+        ]
+    ));
+    body.push(ClarityExpression::FunctionCall(
+        "var-set".to_string(),
+        vec![initialized(), ClarityExpression::Literal("true".to_string())]
+    ));
+}
+
+/// Zero value used to initialize (or reset) a variable of the given Clarity type.
+pub fn default_value(var_type: &ClarityType) -> String {
+    match var_type {
+        ClarityType::Int => "0".to_string(),
+        ClarityType::Bool => "false".to_string(),
+        ClarityType::Principal => "tx-sender".to_string(),
+        ClarityType::StringAscii(_) => "\"\"".to_string(),
+        ClarityType::StringUtf8(_) => "u\"\"".to_string(),
+        ClarityType::List(..) => "(list)".to_string(),
+        ClarityType::Buff(_) => "0x".to_string(),
+        ClarityType::Tuple(fields) => format!("{{{}}}", fields.iter()
+            .map(|(name, field_type)| format!("{}: {}", name, default_value(field_type)))
+            .collect::<Vec<_>>()
+            .join(", ")),
+        ClarityType::Optional(_) => "none".to_string(),
+        _ => "u0".to_string(),
+    }
+}
+
+fn convert_state_variable(var: StateVariable, ctx: &mut ConversionContext) -> Result<ClarityDataVar> {
+    let name = declared_name(&var.name, VALUE_SUFFIX, ctx.options)?;
+    let var_type = ctx.convert_type(&var.var_type);
+    let initial_value = match var.initial_value {
+        Some(Expression::Literal(val)) if val == ZERO_ADDRESS_LITERAL => {
+            ctx.use_zero_address()
         }
+        Some(value) => literal_initializer(value, &var_type)
+            .unwrap_or_else(|| default_value(&var_type)),
+        None => default_value(&var_type),
     };
 
-    ClarityDataVar {
-        name: var.name,
+    Ok(ClarityDataVar {
+        name,
         var_type,
         initial_value,
         is_constant: var.is_constant,
         visibility: var.visibility,
+        enum_members: ctx.enums.get(&var.var_type).map(|members| (var.var_type.clone(), members.clone())),
+    })
+}
+
+/// The Clarity value of a state variable initializer made of literals, such
+/// as `-1` or `[1, 2, 3]`, or `None` if it has to be computed.
+fn literal_initializer(value: Expression, var_type: &ClarityType) -> Option<String> {
+    match (value, var_type) {
+        (Expression::Literal(val), _) => Some(convert_literal(val, Some(var_type))),
+        (Expression::UnaryOp(op, operand), ClarityType::Int) if op == "-" => match *operand {
+            Expression::Literal(val) => Some(convert_literal(format!("-{}", val), Some(var_type))),
+            _ => None,
+        },
+        (Expression::ArrayLiteral(items), ClarityType::List(_, element_type)) => {
+            let items = items.into_iter()
+                .map(|item| literal_initializer(item, element_type))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("(list {})", items.join(" ")))
+        }
+        _ => None,
     }
 }
 
-pub fn convert_function(func: Function) -> Result<ClarityFunction> {
-    Ok(ClarityFunction {
-        name: func.name,
-        params: func.params.into_iter()
-            .map(|p| ClarityParameter {
-                name: p.name,
-                param_type: convert_solidity_type(&p.param_type),
+/// Clarity keywords and built-ins that are valid Solidity identifiers.
+const CLARITY_RESERVED: &[&str] = &[
+    "and", "append", "asserts", "begin", "concat", "contract", "err", "filter", "fold",
+    "get", "hash160", "if", "len", "let", "list", "log2", "map", "match", "merge",
+    "mod", "none", "not", "ok", "or", "pow", "print", "slice", "some", "sqrti",
+    "tuple", "xor",
+];
+
+/// Suffixes appended to reserved names under `ReservedNamePolicy::Rename`.
+const VALUE_SUFFIX: &str = "-var";
+const FUNCTION_SUFFIX: &str = "-fn";
+
+/// The Clarity name for a Solidity identifier in the configured naming
+/// style, suffixed if it is reserved.
+fn clarity_name(name: &str, suffix: &str, naming: NamingStyle) -> String {
+    if CLARITY_RESERVED.contains(&name) {
+        apply_naming(&format!("{}{}", name, suffix), naming)
+    } else {
+        apply_naming(name, naming)
+    }
+}
+
+/// Like `clarity_name`, but for declarations, where the reserved-name
+/// policy decides between renaming and failing.
+fn declared_name(name: &str, suffix: &str, options: &TranspileOptions) -> Result<String> {
+    if options.reserved_names == ReservedNamePolicy::Error && CLARITY_RESERVED.contains(&name) {
+        return Err(anyhow!("{} is a reserved word in Clarity", name));
+    }
+    Ok(clarity_name(name, suffix, options.naming))
+}
+
+fn convert_parameters(params: Vec<Parameter>, ctx: &mut ConversionContext) -> Result<Vec<ClarityParameter>> {
+    params.into_iter()
+        .map(|p| {
+            declared_name(&p.name, VALUE_SUFFIX, ctx.options)?;
+            // A parameter typed with an interface takes any contract
+            // implementing its trait, which must be imported to be named
+            let param_type = if ctx.interfaces.contains(&p.param_type) {
+                let trait_ref = TraitRef {
+                    alias: trait_name(&p.param_type),
+                    contract: format!(".{}", p.param_type.to_lowercase()),
+                };
+                let param_type = ClarityType::Trait(trait_ref.alias.clone());
+                if !ctx.traits.contains(&trait_ref) {
+                    ctx.traits.push(trait_ref);
+                }
+                param_type
+            } else {
+                ctx.convert_type(&p.param_type)
+            };
+            Ok(ClarityParameter {
+                name: ctx.parameter_name(&p.name),
+                param_type,
+            })
+        })
+        .collect()
+}
+
+/// How the parser represents the Solidity `address(0)` expression.
+pub const ZERO_ADDRESS_LITERAL: &str = "address(0)";
+
+/// Clarity has no zero address; the standard mainnet burn address plays
+/// that role, so `address(0)` is emitted as a constant bound to it.
+const ZERO_ADDRESS_PRINCIPAL: &str = "'SP000000000000000000002Q6VF78";
+
+/// Error codes allocated to named errors start here; lower codes are left
+/// for generic failures such as a bare `revert()`.
+const FIRST_ERROR_CODE: u32 = 100;
+
+/// Constant raised by a failed `assert`. Its code is reserved below
+/// `FIRST_ERROR_CODE` so an invariant violation is never mistaken for a
+/// `require` or custom error.
+const PANIC_ERROR: &str = "ERR_PANIC";
+const PANIC_ERROR_CODE: u32 = 2;
+
+/// Code used by `revert()` and `revert("reason")`, which name no error.
+const GENERIC_ERROR_CODE: u32 = 1;
+
+/// Name of the constant holding a `require` message's code
+/// (`"not owner"` -> `ERR_NOT_OWNER`), or `None` if the message has no
+/// usable characters.
+fn message_constant(message: &str) -> Option<String> {
+    let words: Vec<String> = message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_uppercase)
+        .collect();
+    (!words.is_empty()).then(|| format!("ERR_{}", words.join("_")))
+}
+
+/// Name of the constant holding a custom error's code (`Unauthorized` -> `ERR_UNAUTHORIZED`).
+fn error_constant(error_name: &str) -> String {
+    format!("ERR_{}", to_kebab_case(error_name).to_uppercase().replace('-', "_"))
+}
+
+/// Name of the constant holding an enum member's number
+/// (`State.Active` -> `STATE_ACTIVE`).
+fn enum_constant(enum_name: &str, member: &str) -> String {
+    format!("{}_{}", to_kebab_case(enum_name), to_kebab_case(member)).to_uppercase().replace('-', "_")
+}
+
+/// What a state variable identifier refers to in Clarity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    /// A `define-data-var`, read with `var-get`.
+    DataVar,
+    /// A `define-constant`, read by its bare name.
+    Constant,
+}
+
+/// State shared while converting the functions of a single contract.
+struct ConversionContext<'a> {
+    options: &'a TranspileOptions,
+    events: &'a [Event],
+    /// Name of the function whose body is being converted.
+    function_name: String,
+    /// Private helper functions generated while lowering statements.
+    helpers: Vec<ClarityFunction>,
+    /// Constants generated while lowering statements.
+    constants: Vec<ClarityDataVar>,
+    /// Clarity types of the contract's data variables, by name.
+    var_types: HashMap<String, ClarityType>,
+    /// Tuple types of the contract's structs, by struct name.
+    structs: HashMap<String, ClarityType>,
+    /// Members of the contract's enums, by enum name.
+    enums: HashMap<String, Vec<String>>,
+    /// Clarity value types of the contract's maps, by name.
+    map_types: HashMap<String, ClarityType>,
+    /// Values of the current function's locals, substituted where read.
+    locals: HashMap<String, ClarityExpression>,
+    /// `using Library for Type` directives in effect.
+    using: Vec<UsingDirective>,
+    /// Contracts deployed alongside this one, by Solidity name.
+    peers: Vec<String>,
+    /// Interfaces deployed alongside this one as traits, by Solidity name.
+    interfaces: Vec<String>,
+    /// The contract's own functions, by Solidity name. Each returns a
+    /// response, so callers unwrap the value with `try!`.
+    functions: Vec<String>,
+    /// Functions inlined from libraries, as `(library, function)`.
+    library_functions: Vec<(String, String)>,
+    /// Solidity types of the state variables, by Solidity name.
+    state_types: HashMap<String, String>,
+    /// How each state variable is read, by Solidity name. Parameters and
+    /// locals are read through `locals` instead.
+    symbols: HashMap<String, Symbol>,
+    /// Solidity types of the current function's parameters and locals.
+    local_types: HashMap<String, String>,
+    /// Names bound by `let` so far in the current function. Clarity rejects
+    /// rebinding a name, so a shadowing local gets a fresh one.
+    let_names: Vec<String>,
+    /// Tuple keys for the current function's return values.
+    return_names: Vec<String>,
+    /// Conversion warnings for the current function.
+    notes: Vec<String>,
+    /// Every warning and note, for the caller.
+    warnings: Vec<Warning>,
+    /// Constructs the target Clarity version cannot express.
+    errors: Vec<String>,
+    /// Where the declaration being converted starts, to locate errors.
+    span: Option<Span>,
+    /// Next unallocated error code for generated `ERR_` constants. Shared by
+    /// `init` and every function, so codes are unique across the contract.
+    error_code: u32,
+    /// Codes allocated so far, with what each one reports.
+    error_codes: Vec<(u32, String)>,
+    /// Traits that parameters are typed with, in order of first use.
+    traits: Vec<TraitRef>,
+    /// Data vars declared `immutable`, writable only from the constructor.
+    immutables: Vec<String>,
+    in_constructor: bool,
+    loop_count: usize,
+}
+
+impl<'a> ConversionContext<'a> {
+    fn new(options: &'a TranspileOptions, events: &'a [Event]) -> Self {
+        ConversionContext {
+            options,
+            events,
+            function_name: String::new(),
+            helpers: Vec::new(),
+            constants: Vec::new(),
+            var_types: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            map_types: HashMap::new(),
+            locals: HashMap::new(),
+            using: Vec::new(),
+            peers: Vec::new(),
+            interfaces: Vec::new(),
+            functions: Vec::new(),
+            library_functions: Vec::new(),
+            state_types: HashMap::new(),
+            symbols: HashMap::new(),
+            local_types: HashMap::new(),
+            let_names: Vec::new(),
+            return_names: Vec::new(),
+            notes: Vec::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            span: None,
+            error_code: FIRST_ERROR_CODE,
+            error_codes: Vec::new(),
+            traits: Vec::new(),
+            immutables: Vec::new(),
+            in_constructor: false,
+            loop_count: 0,
+        }
+    }
+
+    /// Converts a Solidity type, expanding declared structs to their tuple
+    /// type. Enums are numbered, so they become `uint`, and a variable
+    /// holding a peer contract holds its principal.
+    fn convert_type(&self, solidity_type: &str) -> ClarityType {
+        if self.enums.contains_key(solidity_type) {
+            return ClarityType::Uint;
+        }
+        if self.peers.iter().any(|peer| peer == solidity_type) {
+            return ClarityType::Principal;
+        }
+        self.structs.get(solidity_type)
+            .cloned()
+            .unwrap_or_else(|| convert_solidity_type(solidity_type, self.options))
+    }
+
+    fn enter_function(&mut self, name: &str) {
+        self.function_name = name.to_string();
+        self.locals.clear();
+        self.let_names.clear();
+        self.local_types.clear();
+        self.return_names.clear();
+        self.notes.clear();
+    }
+
+    /// The peer contract a method call on `receiver` goes to: the contract
+    /// named directly, through a variable of its type, or through a
+    /// conversion such as `Token(addr)`.
+    fn called_peer(&self, receiver: &Expression) -> Option<String> {
+        let name = match receiver {
+            Expression::Identifier(name) => self.local_types.get(name)
+                .or_else(|| self.state_types.get(name))
+                .unwrap_or(name),
+            Expression::FunctionCall(callee, args) if args.len() == 1 => match callee.as_ref() {
+                Expression::Identifier(name) => name,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.peers.iter().find(|peer| *peer == name).cloned()
+    }
+
+    /// Whether `return expr;` returns the `map-get?` optional itself, under
+    /// `preserve_map_optionals`, rather than the stored value.
+    fn returns_optional(&self, expr: &Expression) -> bool {
+        self.options.preserve_map_optionals
+            && self.return_names.len() == 1
+            && matches!(expr, Expression::MapAccess(name, _)
+                if self.map_types.contains_key(&clarity_name(name, VALUE_SUFFIX, self.options.naming)))
+    }
+
+    /// Brings the parameters into scope: reading one gives its bare name,
+    /// and assigning to one rebinds it like a local.
+    fn declare_parameters(&mut self, params: &[Parameter]) {
+        for param in params {
+            self.local_types.insert(param.name.clone(), param.param_type.clone());
+            let name = self.parameter_name(&param.name);
+            self.let_names.push(name.clone());
+            self.locals.insert(param.name.clone(), ClarityExpression::Var(name));
+        }
+    }
+
+    /// Clarity name of the parameter `name`. Clarity rejects a parameter
+    /// named like a data var or map, so one shadowing a state variable is
+    /// suffixed with `-param`.
+    fn parameter_name(&self, name: &str) -> String {
+        let clarity = clarity_name(name, VALUE_SUFFIX, self.options.naming);
+        if self.state_types.contains_key(name) {
+            format!("{}-param", clarity)
+        } else {
+            clarity
+        }
+    }
+
+    /// The library a method call on `receiver` resolves to through a
+    /// `using` directive. Libraries in `LIBRARY_BUILTINS` claim just their
+    /// listed functions; others claim any method on a value of their type.
+    fn bound_library(&self, receiver: &Expression, method: &str) -> Option<String> {
+        let receiver_type = match receiver {
+            Expression::Identifier(name) => Some(self.local_types.get(name).or_else(|| self.state_types.get(name))?),
+            Expression::Cast(target_type, _) => Some(target_type),
+            _ => None,
+        };
+        let same_type = |a: &str, b: &str| {
+            matches!((normalize_type(a, self.options), normalize_type(b, self.options)), (Ok(a), Ok(b)) if a == b)
+        };
+        self.using.iter()
+            .find(|directive| {
+                let mut functions = LIBRARY_BUILTINS.iter()
+                    .filter(|(library, _, _)| *library == directive.library)
+                    .peekable();
+                if functions.peek().is_some() {
+                    return functions.any(|(_, function, _)| *function == method);
+                }
+                match (&directive.target, receiver_type) {
+                    (Some(target), Some(receiver_type)) => same_type(target, receiver_type),
+                    (None, Some(_)) => true,
+                    (_, None) => false,
+                }
             })
-            .collect(),
-        public: func.visibility.as_ref().map_or(false, |v| v == "public" || v == "external"),
-        read_only: func.mutability.as_ref().map_or(false, |m| m == "view" || m == "pure"),
-        body: convert_statements(func.body)?,
+            .map(|directive| directive.library.clone())
+    }
+
+    /// Declares a generated constant unless one with that name already exists.
+    fn add_constant(&mut self, name: &str, var_type: ClarityType, value: String) {
+        if !self.constants.iter().any(|c| c.name == name) {
+            self.constants.push(ClarityDataVar {
+                name: name.to_string(),
+                var_type,
+                initial_value: value,
+                is_constant: true,
+                visibility: None,
+                enum_members: None,
+            });
+        }
+    }
+
+    /// Allocates a contract-unique error code.
+    fn next_error_code(&mut self, message: &str) -> u32 {
+        let code = self.error_code;
+        self.error_code += 1;
+        self.error_codes.push((code, message.to_string()));
+        code
+    }
+
+    /// The constant holding the code for `message`, declared on first use.
+    /// Messages without usable characters fall back to the generic code.
+    fn error_for_message(&mut self, message: &str) -> ClarityExpression {
+        let Some(name) = message_constant(message) else {
+            return self.generic_error();
+        };
+        if !self.constants.iter().any(|c| c.name == name) {
+            let code = self.next_error_code(message);
+            self.add_constant(&name, ClarityType::Uint, format!("u{}", code));
+        }
+        ClarityExpression::Var(name)
+    }
+
+    /// The generic error code, recorded with the allocated codes on first use.
+    fn generic_error(&mut self) -> ClarityExpression {
+        if !self.error_codes.iter().any(|(code, _)| *code == GENERIC_ERROR_CODE) {
+            self.error_codes.push((GENERIC_ERROR_CODE, "failed without a specific error".to_string()));
+        }
+        ClarityExpression::Literal(format!("u{}", GENERIC_ERROR_CODE))
+    }
+
+    /// Records an error unless the target Clarity version has `feature`.
+    fn require_version(&mut self, feature: &str, version: ClarityVersion) {
+        if self.options.clarity_version < version {
+            self.error(format!(
+                "{} in {} requires {:?} or later",
+                feature, self.function_name, version
+            ));
+        }
+    }
+
+    /// Records an error, pointing at the declaration being converted.
+    fn error(&mut self, message: String) {
+        let error = locate(&self.span, anyhow!(message)).to_string();
+        self.errors.push(error);
+    }
+
+    /// Stands in for a Solidity global Clarity cannot express: an error in
+    /// strict mode, otherwise `u0` with a warning.
+    fn unsupported(&mut self, global: &str) -> ClarityExpression {
+        if self.options.strict {
+            self.error(format!("{} in {} has no Clarity equivalent", global, self.function_name));
+        } else {
+            let message = format!("{} in {} has no Clarity equivalent and was replaced with u0", global, self.function_name);
+            self.report(Severity::Warning, message, None);
+        }
+        ClarityExpression::Literal("u0".to_string())
+    }
+
+    /// Records a warning for the caller and as a comment above the function
+    /// being converted.
+    fn report(&mut self, severity: Severity, message: String, line: Option<usize>) {
+        let location = line.map(|line| format!("line {}: ", line)).unwrap_or_default();
+        self.notes.push(format!("{}: {}{}", severity.as_str().to_uppercase(), location, message));
+        self.warnings.push(Warning { severity, message, line });
+    }
+
+    /// Declares the `ZERO_ADDRESS` constant and returns its name.
+    fn use_zero_address(&mut self) -> String {
+        self.add_constant("ZERO_ADDRESS", ClarityType::Principal, ZERO_ADDRESS_PRINCIPAL.to_string());
+        "ZERO_ADDRESS".to_string()
+    }
+}
+
+fn convert_function(func: Function, ctx: &mut ConversionContext) -> Result<ClarityFunction> {
+    ctx.enter_function(&func.name);
+    let special = SPECIAL_FUNCTIONS.iter().find(|(solidity, _, _)| *solidity == func.name);
+    if let Some((_, _, note)) = special {
+        ctx.report(Severity::Note, note.to_string(), None);
+    } else if func.mutability.as_deref() == Some("payable") {
+        // Clarity calls carry no value; the caller has to send STX explicitly
+        let message = format!("{} is payable in Solidity; callers must send STX with stx-transfer?", func.name);
+        ctx.report(Severity::Note, message, None);
+    }
+    let return_fields: Vec<(String, ClarityType)> = func.return_type.iter()
+        .enumerate()
+        .map(|(i, (name, t))| (
+            name.clone().unwrap_or_else(|| format!("value-{}", i)),
+            convert_solidity_type(t, ctx.options),
+        ))
+        .collect();
+    ctx.return_names = return_fields.iter().map(|(name, _)| name.clone()).collect();
+    let returns_entry = matches!(find_return(&func.body), Some(StatementKind::Return(Some(expr))) if ctx.returns_optional(expr));
+    let return_type = match return_fields.as_slice() {
+        [] => None,
+        [(_, single)] if returns_entry => Some(ClarityType::Optional(Box::new(single.clone())).to_string()),
+        [(_, single)] => Some(single.to_string()),
+        fields => Some(ClarityType::Tuple(fields.iter()
+            .map(|(name, t)| (apply_naming(name, ctx.options.naming), t.clone()))
+            .collect()).to_string()),
+    };
+
+    // Named return values behave like locals initialized to their zero value
+    for ((name, solidity_type), (_, clarity_type)) in func.return_type.iter().zip(&return_fields) {
+        if let Some(name) = name {
+            ctx.local_types.insert(name.clone(), solidity_type.clone());
+            ctx.locals.insert(name.clone(), ClarityExpression::Literal(default_value(clarity_type)));
+        }
+    }
+
+    let public = matches!(func.visibility.as_deref(), Some("public" | "external"));
+    ctx.declare_parameters(&func.params);
+    let params = convert_parameters(func.params, ctx)?;
+    let mut body = Vec::new();
+    if public && ctx.options.validate_inputs {
+        for param in params.iter().filter(|p| p.param_type == ClarityType::Uint && is_amount_parameter(&p.name)) {
+            body.push(ClarityExpression::FunctionCall(
+                "asserts!".to_string(),
+                vec![
+                    ClarityExpression::FunctionCall(
+                        ">".to_string(),
+                        vec![ClarityExpression::Var(param.name.clone()), ClarityExpression::Literal("u0".to_string())]
+                    ),
+                    ClarityExpression::FunctionCall("err".to_string(), vec![ctx.error_for_message("zero amount")]),
+                ]
+            ));
+        }
+    }
+
+    let ends_with_return = matches!(find_return(&func.body), Some(StatementKind::Return(Some(_))));
+    body.extend(convert_statements(func.body, ctx)?);
+
+    // Falling off the end returns the named return values, or the zero
+    // value of each unnamed one
+    if !return_fields.is_empty() && !ends_with_return {
+        let mut values: Vec<(String, ClarityExpression)> = return_fields.iter()
+            .map(|(name, clarity_type)| (
+                apply_naming(name, ctx.options.naming),
+                ctx.locals.get(name).cloned()
+                    .unwrap_or_else(|| ClarityExpression::Literal(default_value(clarity_type))),
+            ))
+            .collect();
+        push_innermost(&mut body, if values.len() == 1 {
+            values.remove(0).1
+        } else {
+            ClarityExpression::Tuple(values)
+        });
+    }
+
+    if ctx.options.optimize {
+        body = hoist_repeated_reads(body);
+    }
+
+    Ok(ClarityFunction {
+        name: match special {
+            Some((_, clarity, _)) => clarity.to_string(),
+            None => declared_name(&func.name, FUNCTION_SUFFIX, ctx.options)?,
+        },
+        params,
+        public,
+        read_only: matches!(func.mutability.as_deref(), Some("view" | "pure")),
+        return_type,
+        wraps_response: true,
+        body,
+        notes: std::mem::take(&mut ctx.notes),
     })
 }
 
-fn convert_statements(statements: Vec<Statement>) -> Result<Vec<ClarityExpression>> {
+/// Appends `expr` to the innermost `let` ending `body`, where the locals it
+/// may refer to are still bound.
+fn push_innermost(body: &mut Vec<ClarityExpression>, expr: ClarityExpression) {
+    match body.last_mut() {
+        Some(ClarityExpression::Let(_, inner)) => push_innermost(inner, expr),
+        _ => body.push(expr),
+    }
+}
+
+/// Solidity's unnamed `receive`/`fallback` functions, as
+/// `(solidity, clarity, note)`. Clarity never calls a function implicitly,
+/// so each becomes a named function that callers invoke themselves.
+const SPECIAL_FUNCTIONS: &[(&str, &str, &str)] = &[
+    (
+        "receive",
+        "handle-deposit",
+        "converted from receive(); Clarity has no implicit receive hook, \
+         so senders must call handle-deposit alongside their stx-transfer?",
+    ),
+    (
+        "fallback",
+        "handle-fallback",
+        "converted from fallback(); Clarity rejects calls to undefined functions, \
+         so this only runs when called by name",
+    ),
+];
+
+/// Heuristic for `--validate-inputs`: parameters named like an amount or
+/// value, which are expected to be nonzero.
+fn is_amount_parameter(name: &str) -> bool {
+    name.contains("amount") || name == "value" || name.ends_with("-value")
+}
+
+/// Converts `statements` in order. While one is converted, errors point at
+/// it; a failing one stays located so the caller's error points there too.
+fn convert_statements(statements: Vec<Statement>, ctx: &mut ConversionContext) -> Result<Vec<ClarityExpression>> {
     let mut clarity_statements = Vec::new();
+    let outer_span = ctx.span.clone();
 
-    for stmt in statements {
-        match stmt {
-            Statement::Expression(expr) => {
-                clarity_statements.push(convert_expression(expr));
+    let mut statements = statements.into_iter();
+    while let Some(stmt) = statements.next() {
+        if stmt.span.is_some() {
+            ctx.span = stmt.span;
+        }
+        match stmt.kind {
+            // The binding scopes over the rest of the block
+            StatementKind::LocalVar(name, var_type, value) => {
+                let clarity_type = ctx.convert_type(&var_type);
+                ctx.local_types.insert(name.clone(), var_type);
+                let value = match value {
+                    Some(value) => convert_expression_as(value, Some(&clarity_type), ctx),
+                    None => ClarityExpression::Literal(default_value(&clarity_type)),
+                };
+                clarity_statements.push(bind_local(name, value, statements.collect(), ctx)?);
+                break;
             }
-            Statement::Return(expr) => {
-                clarity_statements.push(convert_expression(expr));
+            StatementKind::Expression(expr) => {
+                // Clarity rejects an unchecked response in a `begin`, and a
+                // failed call should abort the caller as a Solidity revert would
+                clarity_statements.push(match convert_expression(expr, ctx) {
+                    ClarityExpression::FunctionCall(name, args) if name == "contract-call?" => {
+                        let call = ClarityExpression::FunctionCall(name, args);
+                        ClarityExpression::FunctionCall("try!".to_string(), vec![call])
+                    }
+                    expr => expr,
+                });
             }
-            Statement::Assignment(var_name, expr) => {
+            StatementKind::Assert(condition) => {
+                if !ctx.constants.iter().any(|c| c.name == PANIC_ERROR) {
+                    ctx.add_constant(PANIC_ERROR, ClarityType::Uint, format!("u{}", PANIC_ERROR_CODE));
+                    ctx.error_codes.push((PANIC_ERROR_CODE, "assertion failed (invariant violated)".to_string()));
+                }
+                let condition = convert_expression(condition, ctx);
                 clarity_statements.push(ClarityExpression::FunctionCall(
-                    "var-set".to_string(),
+                    "asserts!".to_string(),
                     vec![
-                        ClarityExpression::Var(var_name),
-                        convert_expression(expr)
+                        condition,
+                        ClarityExpression::FunctionCall(
+                            "err".to_string(),
+                            vec![ClarityExpression::Var(PANIC_ERROR.to_string())]
+                        ),
                     ]
                 ));
             }
-            Statement::MapAccessAssignment(map_name, key, value) => {
+            StatementKind::Unsupported(note) => {
+                if ctx.options.strict {
+                    ctx.errors.push(format!(
+                        "line {}: {} in {} is not supported", note.line, note.construct, ctx.function_name
+                    ));
+                } else {
+                    let message = format!("{} in {} is not supported and was skipped", note.construct, ctx.function_name);
+                    ctx.report(Severity::Warning, message, Some(note.line));
+                }
+            }
+            // A bare return ends the function like falling off its end;
+            // anything after it is unreachable
+            StatementKind::Return(None) => break,
+            StatementKind::Return(Some(Expression::Tuple(items))) if items.len() == ctx.return_names.len() => {
+                let names = ctx.return_names.clone();
+                let fields = names.into_iter()
+                    .zip(items)
+                    .map(|(name, item)| (apply_naming(&name, ctx.options.naming), convert_operand(item, ctx)))
+                    .collect();
+                clarity_statements.push(ClarityExpression::Tuple(fields));
+                break;
+            }
+            StatementKind::Return(Some(expr)) if ctx.returns_optional(&expr) => {
+                clarity_statements.push(convert_expression(expr, ctx));
+                break;
+            }
+            // Returned as the declared value type, so a missing entry reads
+            // as zero rather than as an optional
+            StatementKind::Return(Some(expr)) => {
+                clarity_statements.push(convert_operand(expr, ctx));
+                break;
+            }
+            // Rebinding rather than substituting the new value keeps reads
+            // of it from seeing later writes to the state it was read from
+            StatementKind::Assignment(var_name, expr) if ctx.locals.contains_key(&var_name) => {
+                let value = convert_expression(expr, ctx);
+                clarity_statements.push(bind_local(var_name, value, statements.collect(), ctx)?);
+                break;
+            }
+            StatementKind::Assignment(var_name, expr) => {
+                let var_name = clarity_name(&var_name, VALUE_SUFFIX, ctx.options.naming);
+                if ctx.immutables.contains(&var_name) && !ctx.in_constructor {
+                    return Err(anyhow!(
+                        "Immutable {} can only be assigned in the constructor, not in {}",
+                        var_name, ctx.function_name
+                    ));
+                }
+                let var_type = ctx.var_types.get(&var_name).cloned();
+                let value = convert_expression_as(expr, var_type.as_ref(), ctx);
+                clarity_statements.push(ClarityExpression::FunctionCall(
+                    "var-set".to_string(),
+                    vec![ClarityExpression::Var(var_name), value]
+                ));
+            }
+            StatementKind::MapAccessAssignment(map_name, key, value) => {
+                clarity_statements.push(ClarityExpression::MapSet(
+                    clarity_name(&map_name, VALUE_SUFFIX, ctx.options.naming),
+                    vec![convert_expression(*key, ctx)],
+                    Box::new(convert_expression(value, ctx))
+                ));
+            }
+            StatementKind::FieldAssignment(map_name, key, field, value) => {
+                // Solidity updates one field in place; Clarity rewrites the whole tuple
+                let map_name = clarity_name(&map_name, VALUE_SUFFIX, ctx.options.naming);
+                let key = convert_expression(*key, ctx);
+                let stored = stored_value(&map_name, key.clone(), ctx);
+                let value = convert_expression(value, ctx);
                 clarity_statements.push(ClarityExpression::MapSet(
                     map_name,
-                    vec![convert_expression(*key)],
-                    Box::new(convert_expression(value))
+                    vec![key],
+                    Box::new(ClarityExpression::FunctionCall(
+                        "merge".to_string(),
+                        vec![stored, ClarityExpression::Tuple(vec![(apply_naming(&field, ctx.options.naming), value)])]
+                    ))
                 ));
             }
-            Statement::Emit(event_name, args) => {
-                let mut print_args = vec![ClarityExpression::Literal(format!("\"{}\"", event_name))];
-                print_args.extend(args.into_iter().map(convert_expression));
-                clarity_statements.push(ClarityExpression::Print(print_args));
+            StatementKind::Emit(event_name, args) => {
+                clarity_statements.push(convert_emit(event_name, args, ctx)?);
+            }
+            StatementKind::While(condition, body) => {
+                clarity_statements.extend(convert_while(condition, body, statements.collect(), ctx)?);
+                break;
+            }
+            // Clarity only scopes names with `let`, so the block is inlined:
+            // its bindings cover the rest of the function, and once it ends
+            // its locals go out of scope and any names they shadowed return.
+            // A return inside it ends the function, so nothing after it runs
+            StatementKind::Block(inner) => {
+                let rest: Vec<Statement> = if find_return(&inner).is_some() {
+                    Vec::new()
+                } else {
+                    statements.collect()
+                };
+                let outer = ctx.locals.clone();
+                let declared: Vec<String> = inner.iter()
+                    .filter_map(|stmt| match &stmt.kind {
+                        StatementKind::LocalVar(name, _, _) => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                let mut block = convert_statements(inner, ctx)?;
+                for name in declared {
+                    match outer.get(&name) {
+                        Some(value) => ctx.locals.insert(name, value.clone()),
+                        None => ctx.locals.remove(&name),
+                    };
+                }
+                for expr in convert_statements(rest, ctx)? {
+                    push_innermost(&mut block, expr);
+                }
+                clarity_statements.append(&mut block);
+                break;
+            }
+            StatementKind::Delete(target) => {
+                clarity_statements.push(convert_delete(target, ctx)?);
+            }
+            StatementKind::Revert(error_name, _) => {
+                // Clarity errors carry a code only, so the error's arguments are dropped
+                let code = match error_name {
+                    Some(name) if ctx.constants.iter().any(|c| c.name == error_constant(&name)) => {
+                        ClarityExpression::Var(error_constant(&name))
+                    }
+                    Some(name) => return Err(anyhow!("Unknown custom error {} in {}", name, ctx.function_name)),
+                    None => ctx.generic_error(),
+                };
+                clarity_statements.push(ClarityExpression::FunctionCall(
+                    "asserts!".to_string(),
+                    vec![
+                        ClarityExpression::Literal("false".to_string()),
+                        ClarityExpression::FunctionCall("err".to_string(), vec![code]),
+                    ]
+                ));
             }
         }
     }
 
+    ctx.span = outer_span;
     Ok(clarity_statements)
 }
 
-fn convert_expression(expr: Expression) -> ClarityExpression {
-    match expr {
-        Expression::Literal(val) => {
-            if val == "true" {
-                ClarityExpression::Literal("true".to_string())
-            } else if val == "false" {
-                ClarityExpression::Literal("false".to_string())
-            } else if val.chars().all(|c| c.is_digit(10)) {
-                ClarityExpression::Literal(format!("u{}", val))
-            } else {
-                ClarityExpression::Literal(val)
+/// The `return` reached first in `statements`, looking into nested blocks.
+/// The grammar has no conditionals, so reaching one always ends the function.
+fn find_return(statements: &[Statement]) -> Option<&StatementKind> {
+    statements.iter().find_map(|stmt| match &stmt.kind {
+        StatementKind::Return(_) => Some(&stmt.kind),
+        StatementKind::Block(inner) => find_return(inner),
+        _ => None,
+    })
+}
+
+/// Binds a local to `value` with a `let` scoping over the remaining
+/// statements, renaming it if an enclosing binding or a state variable
+/// already uses the name.
+fn bind_local(name: String, value: ClarityExpression, rest: Vec<Statement>, ctx: &mut ConversionContext) -> Result<ClarityExpression> {
+    let local = fresh_let_name(&name, ctx);
+    ctx.locals.insert(name, ClarityExpression::Var(local.clone()));
+    let mut bindings = vec![(local, value)];
+    let mut body = convert_statements(rest, ctx)?;
+    // Consecutive bindings share one let, which binds sequentially
+    if let [ClarityExpression::Let(inner_bindings, inner_body)] = body.as_mut_slice() {
+        bindings.append(inner_bindings);
+        body = std::mem::take(inner_body);
+    }
+    Ok(ClarityExpression::Let(bindings, body))
+}
+
+/// Reserves a `let` name for the local `name`, suffixed with a counter if
+/// an enclosing binding or a state variable already uses it.
+fn fresh_let_name(name: &str, ctx: &mut ConversionContext) -> String {
+    let mut local = clarity_name(name, VALUE_SUFFIX, ctx.options.naming);
+    if ctx.let_names.contains(&local) || ctx.state_types.contains_key(name) {
+        local = (1..)
+            .map(|n| format!("{}-{}", local, n))
+            .find(|fresh| !ctx.let_names.contains(fresh))
+            .expect("unbounded range");
+    }
+    ctx.let_names.push(local.clone());
+    local
+}
+
+/// Lowers `delete x` to a reset to the type's zero value and
+/// `delete m[k]` to `map-delete`.
+fn convert_delete(target: Expression, ctx: &mut ConversionContext) -> Result<ClarityExpression> {
+    match target {
+        Expression::Identifier(name) => {
+            let var_name = clarity_name(&name, VALUE_SUFFIX, ctx.options.naming);
+            let var_type = ctx.var_types.get(&var_name)
+                .ok_or_else(|| anyhow!("Cannot delete unknown state variable {}", name))?;
+            Ok(ClarityExpression::FunctionCall(
+                "var-set".to_string(),
+                vec![
+                    ClarityExpression::Var(var_name),
+                    ClarityExpression::Literal(default_value(var_type)),
+                ]
+            ))
+        }
+        Expression::MapAccess(map_name, key) => {
+            // A nested mapping is one map keyed by the whole path, so only
+            // complete paths name an entry
+            let mut keys = Vec::new();
+            flatten_map_keys((*key).clone(), &mut keys);
+            let depth = ctx.state_types.get(&map_name).map_or(0, |t| t.matches("mapping(").count());
+            if keys.len() < depth {
+                return Err(anyhow!(
+                    "Cannot delete {} in {}: a flattened nested mapping can only delete single entries, keyed by all {} levels",
+                    Expression::MapAccess(map_name, key), ctx.function_name, depth
+                ));
             }
+            Ok(ClarityExpression::FunctionCall(
+                "map-delete".to_string(),
+                vec![ClarityExpression::Var(clarity_name(&map_name, VALUE_SUFFIX, ctx.options.naming)), convert_expression(*key, ctx)]
+            ))
+        }
+        other => Err(anyhow!("Unsupported delete target: {}", other)),
+    }
+}
+
+/// Lowers `while (cond) { body }` into a `fold` over a fixed range.
+///
+/// Clarity has no unbounded loops, so the body becomes a private step
+/// function that runs while `cond` holds and stops running once it turns
+/// false or after `max_iterations` steps, whichever comes first. Locals the
+/// loop uses ride along in the fold's accumulator, and those it assigns are
+/// rebound from the final state for the statements in `rest`.
+fn convert_while(condition: Expression, body: Vec<Statement>, rest: Vec<Statement>, ctx: &mut ConversionContext) -> Result<Vec<ClarityExpression>> {
+    // The body runs inside a fold, which has no way to leave the function
+    if find_return(&body).is_some() {
+        return Err(anyhow!("return inside a while loop in {} is not supported", ctx.function_name));
+    }
+    let bound = ctx.options.max_iterations;
+    let step_name = apply_naming(
+        &format!("{}-while-{}", ctx.function_name, ctx.loop_count),
+        ctx.options.naming
+    );
+    ctx.loop_count += 1;
+
+    let range: Vec<String> = (0..bound).map(|i| format!("u{}", i)).collect();
+    ctx.add_constant(
+        "LOOP_RANGE",
+        ClarityType::List(bound, Box::new(ClarityType::Uint)),
+        format!("(list {})", range.join(" "))
+    );
+
+    // The step function is a separate definition, so each local is read
+    // through a name of its own that the step binds from the accumulator
+    let outer_locals = ctx.locals.clone();
+    let mut names: Vec<String> = outer_locals.keys().cloned().collect();
+    names.sort();
+    let mut carried = Vec::new();
+    for name in names {
+        let carry = match &outer_locals[&name] {
+            ClarityExpression::Var(var) => var.clone(),
+            _ => fresh_let_name(&name, ctx),
+        };
+        ctx.locals.insert(name.clone(), ClarityExpression::Var(carry.clone()));
+        carried.push((name, carry));
+    }
+
+    let outer_notes = std::mem::take(&mut ctx.notes);
+    let condition = convert_expression(condition, ctx);
+    // Declarations in the body go out of scope with each iteration
+    let mut step_body = convert_statements(vec![StatementKind::Block(body).into()], ctx)?;
+    let step_notes = std::mem::replace(&mut ctx.notes, outer_notes);
+
+    let step_locals = std::mem::replace(&mut ctx.locals, outer_locals);
+    let mut assigned = Vec::new();
+    carried.retain(|(name, carry)| {
+        let value = &step_locals[name];
+        if *value != ClarityExpression::Var(carry.clone()) {
+            assigned.push(name.clone());
+            true
+        } else {
+            mentions(&condition, carry) || step_body.iter().any(|expr| mentions(expr, carry))
+        }
+    });
+
+    let mut notes = vec![format!(
+        "WARNING: body of a while loop in {}, bounded to {} iterations",
+        ctx.function_name, bound
+    )];
+    notes.extend(step_notes);
+    let message = format!("while loop lowered to a fold bounded to {} iterations", bound);
+    ctx.report(Severity::Warning, message, None);
+
+    if carried.is_empty() {
+        step_body.push(ClarityExpression::Literal("true".to_string()));
+        ctx.helpers.push(ClarityFunction {
+            name: step_name.clone(),
+            params: vec![
+                ClarityParameter { name: "iteration".to_string(), param_type: ClarityType::Uint },
+                ClarityParameter { name: "running".to_string(), param_type: ClarityType::Bool },
+            ],
+            public: false,
+            read_only: false,
+            return_type: None,
+            wraps_response: false,
+            body: vec![ClarityExpression::FunctionCall(
+                "if".to_string(),
+                vec![
+                    ClarityExpression::FunctionCall(
+                        "and".to_string(),
+                        vec![ClarityExpression::Var("running".to_string()), condition]
+                    ),
+                    ClarityExpression::FunctionCall("begin".to_string(), step_body),
+                    ClarityExpression::Literal("false".to_string()),
+                ]
+            )],
+            notes,
+        });
+
+        let mut statements = vec![ClarityExpression::FunctionCall(
+            "fold".to_string(),
+            vec![
+                ClarityExpression::Var(step_name),
+                ClarityExpression::Var("LOOP_RANGE".to_string()),
+                ClarityExpression::Literal("true".to_string()),
+            ]
+        )];
+        statements.extend(convert_statements(rest, ctx)?);
+        return Ok(statements);
+    }
+
+    // Otherwise the accumulator carries the locals along with the running
+    // flag, and the locals the body assigns are rebound from the final state
+    let state = ClarityExpression::Var("loop-state".to_string());
+    let field = |key: &str| ClarityExpression::FunctionCall(
+        "get".to_string(),
+        vec![ClarityExpression::Var(key.to_string()), state.clone()]
+    );
+    let mut state_type = vec![("running".to_string(), ClarityType::Bool)];
+    for (name, carry) in &carried {
+        let solidity_type = ctx.local_types.get(name).cloned().unwrap_or_default();
+        state_type.push((carry.clone(), ctx.convert_type(&solidity_type)));
+    }
+    let mut next = vec![("running".to_string(), ClarityExpression::Literal("true".to_string()))];
+    next.extend(carried.iter().map(|(name, carry)| (carry.clone(), step_locals[name].clone())));
+    push_innermost(&mut step_body, ClarityExpression::Tuple(next));
+    ctx.helpers.push(ClarityFunction {
+        name: step_name.clone(),
+        params: vec![
+            ClarityParameter { name: "iteration".to_string(), param_type: ClarityType::Uint },
+            ClarityParameter { name: "loop-state".to_string(), param_type: ClarityType::Tuple(state_type) },
+        ],
+        public: false,
+        read_only: false,
+        return_type: None,
+        wraps_response: false,
+        body: vec![ClarityExpression::Let(
+            carried.iter().map(|(_, carry)| (carry.clone(), field(carry))).collect(),
+            vec![ClarityExpression::FunctionCall(
+                "if".to_string(),
+                vec![
+                    ClarityExpression::FunctionCall("and".to_string(), vec![field("running"), condition]),
+                    ClarityExpression::FunctionCall("begin".to_string(), step_body),
+                    ClarityExpression::FunctionCall(
+                        "merge".to_string(),
+                        vec![
+                            state.clone(),
+                            ClarityExpression::Tuple(vec![
+                                ("running".to_string(), ClarityExpression::Literal("false".to_string())),
+                            ]),
+                        ]
+                    ),
+                ]
+            )]
+        )],
+        notes,
+    });
+
+    let mut initial = vec![("running".to_string(), ClarityExpression::Literal("true".to_string()))];
+    initial.extend(carried.iter().map(|(name, carry)| (carry.clone(), ctx.locals[name].clone())));
+    let fold = ClarityExpression::FunctionCall(
+        "fold".to_string(),
+        vec![
+            ClarityExpression::Var(step_name.clone()),
+            ClarityExpression::Var("LOOP_RANGE".to_string()),
+            ClarityExpression::Tuple(initial),
+        ]
+    );
+    if assigned.is_empty() {
+        let mut statements = vec![fold];
+        statements.extend(convert_statements(rest, ctx)?);
+        return Ok(statements);
+    }
+    let result = fresh_let_name(&format!("{}-state", step_name), ctx);
+    let mut bindings = vec![(result.clone(), fold)];
+    for (name, carry) in carried.iter().filter(|(name, _)| assigned.contains(name)) {
+        let local = fresh_let_name(name, ctx);
+        bindings.push((local.clone(), ClarityExpression::FunctionCall(
+            "get".to_string(),
+            vec![ClarityExpression::Var(carry.clone()), ClarityExpression::Var(result.clone())]
+        )));
+        ctx.locals.insert(name.clone(), ClarityExpression::Var(local));
+    }
+    let body = convert_statements(rest, ctx)?;
+    // Values nothing reads again need no rebinding
+    if body.is_empty() && !assigned.iter().any(|name| ctx.return_names.contains(name)) {
+        return Ok(vec![bindings.remove(0).1]);
+    }
+    Ok(vec![ClarityExpression::Let(bindings, body)])
+}
+
+/// Whether `expr` refers to the variable `name`.
+fn mentions(expr: &ClarityExpression, name: &str) -> bool {
+    match expr {
+        ClarityExpression::Var(var) => var == name,
+        ClarityExpression::Literal(_) => false,
+        ClarityExpression::FunctionCall(_, args)
+        | ClarityExpression::MapGet(_, args)
+        | ClarityExpression::Print(args) => args.iter().any(|arg| mentions(arg, name)),
+        ClarityExpression::MapSet(_, keys, value) => {
+            keys.iter().any(|key| mentions(key, name)) || mentions(value, name)
+        }
+        ClarityExpression::Tuple(fields) => fields.iter().any(|(_, value)| mentions(value, name)),
+        ClarityExpression::Let(bindings, body) => {
+            bindings.iter().any(|(_, value)| mentions(value, name)) || body.iter().any(|expr| mentions(expr, name))
+        }
+    }
+}
+
+/// Lowers `emit Event(args)` into `(print {event: "Event", field: arg, ...})`,
+/// keyed by the parameter names of the matching event declaration.
+fn convert_emit(event_name: String, args: Vec<Expression>, ctx: &mut ConversionContext) -> Result<ClarityExpression> {
+    let events = ctx.events;
+    let event = events.iter()
+        .find(|e| e.name == event_name)
+        .ok_or_else(|| anyhow!("emit {}: event is not declared", event_name))?;
+    if event.params.len() != args.len() {
+        return Err(anyhow!(
+            "emit {}: expected {} args, got {}",
+            event_name, event.params.len(), args.len()
+        ));
+    }
+
+    let name = ClarityExpression::Literal(format!("\"{}\"", event_name));
+    let data: Vec<(String, ClarityExpression)> = event.params.iter()
+        .map(|p| apply_naming(&p.name, ctx.options.naming))
+        .zip(args.into_iter().map(|arg| convert_expression(arg, ctx)))
+        .collect();
+    let fields = if ctx.options.event_envelope {
+        let mut envelope = vec![
+            ("topic".to_string(), ClarityExpression::Literal("\"event\"".to_string())),
+            ("name".to_string(), name),
+        ];
+        // Clarity has no empty tuple, so events without fields carry no data
+        if !data.is_empty() {
+            envelope.push(("data".to_string(), ClarityExpression::Tuple(data)));
+        }
+        envelope
+    } else {
+        std::iter::once(("event".to_string(), name)).chain(data).collect()
+    };
+    Ok(ClarityExpression::Print(vec![ClarityExpression::Tuple(fields)]))
+}
+
+/// Solidity time and ether unit suffixes with their multipliers.
+const NUMBER_UNITS: &[(&str, u128)] = &[
+    ("wei", 1),
+    ("gwei", 1_000_000_000),
+    ("ether", 1_000_000_000_000_000_000),
+    ("seconds", 1),
+    ("minutes", 60),
+    ("hours", 3_600),
+    ("days", 86_400),
+    ("weeks", 604_800),
+];
+
+/// Evaluates a Solidity numeric literal (`42`, `1_000`, `0xff`, `5e18`),
+/// folding an optional unit suffix (`7 days`, `2 ether`).
+///
+/// Returns `None` for non-numeric literals and for values that do not fit
+/// in a 128-bit Clarity uint.
+pub fn parse_number_literal(val: &str) -> Option<u128> {
+    if let Some((number, unit)) = val.split_once(' ') {
+        let (_, multiplier) = NUMBER_UNITS.iter().find(|(name, _)| *name == unit)?;
+        return parse_number_literal(number)?.checked_mul(*multiplier);
+    }
+
+    let digits = val.replace('_', "");
+    if let Some(hex) = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        return u128::from_str_radix(hex, 16).ok();
+    }
+    if digits.is_empty() || !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    match digits.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            let mantissa: u128 = mantissa.parse().ok()?;
+            let exponent: u32 = exponent.parse().ok()?;
+            mantissa.checked_mul(10u128.checked_pow(exponent)?)
+        }
+        None => digits.parse().ok(),
+    }
+}
+
+/// Converts a literal, using the Clarity type it is stored into when known.
+///
+/// Numbers become uints unless `expected_type` is `bool`, where Solidity's
+/// C-style truthiness applies; strings stored as `string-utf8` get the `u`
+/// prefix.
+fn convert_literal(val: String, expected_type: Option<&ClarityType>) -> String {
+    if val == "true" || val == "false" {
+        return val;
+    }
+    if expected_type == Some(&ClarityType::Int) {
+        // Signed ints take the bare literal, with its sign
+        if let Some(n) = val.strip_prefix('-').and_then(parse_number_literal) {
+            return format!("-{}", n);
+        }
+    }
+    if let Some(n) = parse_number_literal(&val) {
+        return match expected_type {
+            Some(ClarityType::Bool) => (n != 0).to_string(),
+            Some(ClarityType::Int) => n.to_string(),
+            _ => format!("u{}", n),
+        };
+    }
+    match expected_type {
+        Some(ClarityType::StringUtf8(_)) if val.starts_with('"') => format!("u{}", val),
+        _ => val,
+    }
+}
+
+/// `convert_expression` with a hint for the Clarity type of the result,
+/// used where the expression is stored into a typed variable.
+fn convert_expression_as(expr: Expression, expected_type: Option<&ClarityType>, ctx: &mut ConversionContext) -> ClarityExpression {
+    match expr {
+        Expression::Literal(val) if val != ZERO_ADDRESS_LITERAL => {
+            ClarityExpression::Literal(convert_literal(val, expected_type))
+        }
+        Expression::UnaryOp(op, operand) if op == "-" && expected_type == Some(&ClarityType::Int) => match *operand {
+            Expression::Literal(val) => ClarityExpression::Literal(convert_literal(format!("-{}", val), expected_type)),
+            operand => convert_expression(Expression::UnaryOp(op, Box::new(operand)), ctx),
+        },
+        Expression::ArrayLiteral(items) => {
+            let element_type = match expected_type {
+                Some(ClarityType::List(_, element_type)) => Some(element_type.as_ref()),
+                _ => None,
+            };
+            ClarityExpression::FunctionCall(
+                "list".to_string(),
+                items.into_iter().map(|item| convert_expression_as(item, element_type, ctx)).collect()
+            )
+        }
+        other => convert_expression(other, ctx),
+    }
+}
+
+fn convert_expression(expr: Expression, ctx: &mut ConversionContext) -> ClarityExpression {
+    match expr {
+        Expression::Literal(val) if val == ZERO_ADDRESS_LITERAL => {
+            ClarityExpression::Var(ctx.use_zero_address())
+        }
+        Expression::Literal(val) => ClarityExpression::Literal(convert_literal(val, None)),
+        Expression::Identifier(name) if ctx.locals.contains_key(&name) => {
+            ctx.locals[&name].clone()
+        }
+        // The contract itself, as used in `owner == this`
+        Expression::Identifier(name) if name == "this" => ClarityExpression::Var(CONTRACT_PRINCIPAL.to_string()),
+        Expression::Identifier(name) if ctx.symbols.get(&name) == Some(&Symbol::Constant) => {
+            ClarityExpression::Var(clarity_name(&name, VALUE_SUFFIX, ctx.options.naming))
         }
         Expression::Identifier(name) => {
             ClarityExpression::FunctionCall(
                 "var-get".to_string(),
-                vec![ClarityExpression::Var(name)]
+                vec![ClarityExpression::Var(clarity_name(&name, VALUE_SUFFIX, ctx.options.naming))]
             )
         }
         Expression::BinaryOp(left, op, right) => {
+            let membership = ctx.options.membership_checks
+                .then(|| membership_test(&op, &left, &right))
+                .flatten();
+            if let Some((map_name, key)) = membership {
+                let map_name = clarity_name(map_name, VALUE_SUFFIX, ctx.options.naming);
+                let message = format!(
+                    "{} in {} became a membership check on {}; entries explicitly set to zero count as present",
+                    Expression::BinaryOp(left.clone(), op.clone(), right.clone()), ctx.function_name, map_name
+                );
+                ctx.report(Severity::Note, message, None);
+                let lookup = ClarityExpression::MapGet(map_name, vec![convert_expression(key.clone(), ctx)]);
+                let check = if op == "==" { "is-none" } else { "is-some" };
+                return ClarityExpression::FunctionCall(check.to_string(), vec![lookup]);
+            }
             match op.as_str() {
                 "," => {
+                    // Composite key of a flattened nested mapping
+                    let mut keys = Vec::new();
+                    flatten_map_keys(Expression::BinaryOp(left, op, right), &mut keys);
+                    let names = nested_key_names(keys.len());
+                    ClarityExpression::Tuple(names.into_iter()
+                        .zip(keys)
+                        .map(|(name, key)| (name, convert_expression(key, ctx)))
+                        .collect())
+                }
+                "!=" => ClarityExpression::FunctionCall(
+                    "not".to_string(),
+                    vec![ClarityExpression::FunctionCall(
+                        "is-eq".to_string(),
+                        vec![convert_operand(*left, ctx), convert_operand(*right, ctx)]
+                    )]
+                ),
+                _ => {
+                    let left = convert_operand(*left, ctx);
+                    let right = convert_operand(*right, ctx);
+                    if BITWISE_OPERATORS.contains(&op.as_str()) {
+                        ctx.require_version(&convert_operator(&op), ClarityVersion::Clarity2);
+                    }
+                    match fold_constant(&op, &left, &right) {
+                        Some(folded) => ClarityExpression::Literal(format!("u{}", folded)),
+                        // `and`/`or` are variadic, so `a && b && c` becomes one call
+                        None if op == "&&" || op == "||" => {
+                            let name = convert_operator(&op);
+                            let mut args = Vec::new();
+                            for operand in [left, right] {
+                                match operand {
+                                    ClarityExpression::FunctionCall(inner, operands) if inner == name => args.extend(operands),
+                                    operand => args.push(operand),
+                                }
+                            }
+                            ClarityExpression::FunctionCall(name, args)
+                        }
+                        None => ClarityExpression::FunctionCall(convert_operator(&op), vec![left, right]),
+                    }
+                }
+            }
+        }
+        Expression::UnaryOp(op, operand) => {
+            match (op.as_str(), *operand) {
+                ("!", operand) => ClarityExpression::FunctionCall(
+                    "not".to_string(),
+                    vec![convert_expression(operand, ctx)]
+                ),
+                ("~", operand) => {
+                    ctx.require_version("bit-not", ClarityVersion::Clarity2);
                     ClarityExpression::FunctionCall(
-                        "tuple".to_string(),
-                        vec![convert_expression(*left), convert_expression(*right)]
+                        "bit-not".to_string(),
+                        vec![convert_operand(operand, ctx)]
                     )
                 }
-                _ => ClarityExpression::FunctionCall(
-                    op,
-                    vec![convert_expression(*left), convert_expression(*right)]
-                )
+                ("-", Expression::Literal(val)) if parse_number_literal(&val).is_some() => {
+                    // Negative literals are signed ints in Clarity
+                    let n = parse_number_literal(&val).unwrap_or_default();
+                    ClarityExpression::Literal(format!("-{}", n))
+                }
+                (_, operand) => ClarityExpression::FunctionCall(
+                    "-".to_string(),
+                    vec![ClarityExpression::Literal("0".to_string()), convert_expression(operand, ctx)]
+                ),
+            }
+        }
+        Expression::FunctionCall(callee, args) => convert_call(*callee, args, ctx),
+        Expression::Cast(_, inner) if matches!(*inner, Expression::Identifier(ref name) if name == "this") => {
+            ClarityExpression::Var(CONTRACT_PRINCIPAL.to_string())
+        }
+        Expression::Cast(target_type, inner) => {
+            // Clarity has a single uint width, so narrowing casts cannot truncate
+            let narrowing = (target_type.starts_with("uint") || target_type.starts_with("int"))
+                && !matches!(target_type.as_str(), "uint" | "uint256" | "int" | "int256");
+            if narrowing {
+                let message = format!("{} cast in {} is not range-checked", target_type, ctx.function_name);
+                ctx.report(Severity::Warning, message, None);
             }
+            convert_expression(*inner, ctx)
+        }
+        Expression::ArrayLiteral(items) => convert_expression_as(Expression::ArrayLiteral(items), None, ctx),
+        Expression::Tuple(items) => {
+            ClarityExpression::Tuple(items.into_iter()
+                .enumerate()
+                .map(|(i, item)| (format!("value-{}", i), convert_expression(item, ctx)))
+                .collect())
+        }
+        Expression::Ternary(condition, then_expr, else_expr) => {
+            ClarityExpression::FunctionCall(
+                "if".to_string(),
+                vec![
+                    convert_expression(*condition, ctx),
+                    convert_expression(*then_expr, ctx),
+                    convert_expression(*else_expr, ctx),
+                ]
+            )
+        }
+        Expression::MapAccess(name, index) if is_list(ctx.var_types.get(&clarity_name(&name, VALUE_SUFFIX, ctx.options.naming))) => {
+            // element-at was renamed element-at? in Clarity 2
+            let element_at = match ctx.options.clarity_version {
+                ClarityVersion::Clarity1 => "element-at",
+                _ => "element-at?",
+            };
+            ClarityExpression::FunctionCall(
+                "unwrap-panic".to_string(),
+                vec![ClarityExpression::FunctionCall(
+                    element_at.to_string(),
+                    vec![
+                        convert_expression(Expression::Identifier(name), ctx),
+                        convert_expression(*index, ctx),
+                    ]
+                )]
+            )
         }
         Expression::MapAccess(map_name, key) => {
             ClarityExpression::MapGet(
-                map_name,
-                vec![convert_expression(*key)]
+                clarity_name(&map_name, VALUE_SUFFIX, ctx.options.naming),
+                vec![convert_expression(*key, ctx)]
             )
         }
-        Expression::MemberAccess(expr, member) => {
-            if let Expression::Identifier(name) = *expr {
-                if name == "msg" && member == "sender" {
-                    ClarityExpression::Var("tx-sender".to_string())
-                } else {
-                    ClarityExpression::Var(format!("{}-{}", name, member))
+        Expression::MemberAccess(expr, member) => match *expr {
+            Expression::Identifier(name) if ctx.enums.contains_key(&name) => {
+                let constant = enum_constant(&name, &member);
+                match ctx.enums[&name].iter().position(|m| *m == member) {
+                    Some(index) => ctx.add_constant(&constant, ClarityType::Uint, format!("u{}", index)),
+                    None => ctx.error(format!("Unknown member {} of enum {}", member, name)),
                 }
-            } else {
-                ClarityExpression::Var(format!("{}-{}", expr, member))
+                ClarityExpression::Var(constant)
+            }
+            Expression::Identifier(name) => {
+                if name == "tx" && member == "origin" {
+                    let message = format!(
+                        "tx.origin in {} became tx-sender, which is the immediate caller \
+                         unless the call goes through as-contract; do not rely on it for authorization",
+                        ctx.function_name
+                    );
+                    ctx.report(Severity::Warning, message, None);
+                }
+                if UNSUPPORTED_MEMBERS.contains(&(name.as_str(), member.as_str())) {
+                    return ctx.unsupported(&format!("{}.{}", name, member));
+                }
+                match reserved_member(&name, &member, ctx.options.clarity_version) {
+                    Some(clarity) => ClarityExpression::Var(clarity.to_string()),
+                    None => ClarityExpression::Var(format!("{}-{}", name, member)),
+                }
+            }
+            // address(this).balance reads the same as this.balance
+            Expression::Cast(_, inner) => convert_expression(Expression::MemberAccess(inner, member), ctx),
+            Expression::MapAccess(map_name, key) => {
+                // Field of a struct stored in a mapping
+                let map_name = clarity_name(&map_name, VALUE_SUFFIX, ctx.options.naming);
+                let key = convert_expression(*key, ctx);
+                ClarityExpression::FunctionCall(
+                    "get".to_string(),
+                    vec![ClarityExpression::Var(apply_naming(&member, ctx.options.naming)), stored_value(&map_name, key, ctx)]
+                )
+            }
+            other => ClarityExpression::Var(format!("{}-{}", other, member)),
+        },
+    }
+}
+
+/// For `m[k] != 0` or `m[k] == address(0)`, in either operand order, the map
+/// and key. Missing entries read as zero, so these test whether `k` is in
+/// `m`, and become `is-some`/`is-none` with `membership_checks`.
+fn membership_test<'e>(op: &str, left: &'e Expression, right: &'e Expression) -> Option<(&'e String, &'e Expression)> {
+    if op != "==" && op != "!=" {
+        return None;
+    }
+    let is_zero = |expr: &Expression| matches!(
+        expr,
+        Expression::Literal(val) if val == ZERO_ADDRESS_LITERAL || parse_number_literal(val) == Some(0)
+    );
+    match (left, right) {
+        (Expression::MapAccess(map, key), zero) | (zero, Expression::MapAccess(map, key)) if is_zero(zero) => {
+            Some((map, key))
+        }
+        _ => None,
+    }
+}
+
+/// `require(cond)` aborts with the generic error code; `require(cond, "msg")`
+/// gets a constant per distinct message.
+fn convert_require(mut args: Vec<Expression>, ctx: &mut ConversionContext) -> ClarityExpression {
+    let message = match args.get(1) {
+        Some(Expression::Literal(val)) if val.starts_with('"') => Some(val.trim_matches('"').to_string()),
+        _ => None,
+    };
+    let code = match message {
+        Some(message) => ctx.error_for_message(&message),
+        None => ctx.generic_error(),
+    };
+    let condition = convert_expression(args.swap_remove(0), ctx);
+    ClarityExpression::FunctionCall(
+        "asserts!".to_string(),
+        vec![condition, ClarityExpression::FunctionCall("err".to_string(), vec![code])]
+    )
+}
+
+/// Converts an operator operand, reading mapping entries as plain values
+/// since `map-get?` yields an optional the operator cannot consume.
+fn convert_operand(expr: Expression, ctx: &mut ConversionContext) -> ClarityExpression {
+    match convert_expression(expr, ctx) {
+        ClarityExpression::MapGet(map_name, mut keys) if keys.len() == 1 => {
+            stored_value(&map_name, keys.remove(0), ctx)
+        }
+        other => other,
+    }
+}
+
+/// The value stored under `key`, or the zero value of the map's value type
+/// when absent, matching Solidity's reads of missing mapping entries.
+fn stored_value(map_name: &str, key: ClarityExpression, ctx: &ConversionContext) -> ClarityExpression {
+    let value_type = ctx.map_types.get(map_name).unwrap_or(&ClarityType::Uint);
+    ClarityExpression::FunctionCall(
+        "default-to".to_string(),
+        vec![
+            ClarityExpression::Literal(default_value(value_type)),
+            ClarityExpression::MapGet(map_name.to_string(), vec![key]),
+        ]
+    )
+}
+
+fn is_list(var_type: Option<&ClarityType>) -> bool {
+    matches!(var_type, Some(ClarityType::List(..)))
+}
+
+/// Solidity globals with a Clarity counterpart, as `(object, member, clarity)`.
+/// Block members are the Clarity 1 and 2 forms; see `CLARITY3_MEMBERS`.
+const RESERVED_MEMBERS: &[(&str, &str, &str)] = &[
+    ("msg", "sender", "tx-sender"),
+    ("block", "number", "block-height"),
+    ("block", "timestamp", "(default-to u0 (get-block-info? time (- block-height u1)))"),
+    ("block", "coinbase", "(unwrap-panic (get-block-info? miner-address (- block-height u1)))"),
+    ("block", "difficulty", "(unwrap-panic (get-block-info? vrf-seed (- block-height u1)))"),
+    ("block", "prevrandao", "(unwrap-panic (get-block-info? vrf-seed (- block-height u1)))"),
+    ("this", "balance", "(stx-get-balance (as-contract tx-sender))"),
+    ("tx", "origin", "tx-sender"),
+];
+
+/// Block members in Clarity 3, which replaced `block-height` and
+/// `get-block-info?` with Stacks block and tenure lookups.
+const CLARITY3_MEMBERS: &[(&str, &str, &str)] = &[
+    ("block", "number", "stacks-block-height"),
+    ("block", "timestamp", "(default-to u0 (get-stacks-block-info? time (- stacks-block-height u1)))"),
+    ("block", "coinbase", "(unwrap-panic (get-tenure-info? miner-address (- stacks-block-height u1)))"),
+    ("block", "difficulty", "(unwrap-panic (get-tenure-info? vrf-seed (- stacks-block-height u1)))"),
+    ("block", "prevrandao", "(unwrap-panic (get-tenure-info? vrf-seed (- stacks-block-height u1)))"),
+];
+
+/// Solidity globals that have no Clarity counterpart.
+const UNSUPPORTED_MEMBERS: &[(&str, &str)] = &[
+    ("tx", "gasprice"),
+    ("block", "basefee"),
+    ("block", "gaslimit"),
+    ("msg", "gas"),
+];
+
+/// The contract's own principal, for `this` and `address(this)`.
+const CONTRACT_PRINCIPAL: &str = "(as-contract tx-sender)";
+
+fn reserved_member(object: &str, member: &str, version: ClarityVersion) -> Option<&'static str> {
+    let clarity3 = match version {
+        ClarityVersion::Clarity3 => CLARITY3_MEMBERS,
+        _ => &[],
+    };
+    clarity3.iter()
+        .chain(RESERVED_MEMBERS)
+        .find(|(o, m, _)| *o == object && *m == member)
+        .map(|(_, _, clarity)| *clarity)
+}
+
+/// Identifiers that name Solidity globals rather than other contracts, so a
+/// method call on them is never lowered to `contract-call?`.
+const RESERVED_RECEIVERS: &[&str] = &["msg", "block", "tx", "abi", "this", "super", "type"];
+
+fn convert_call(callee: Expression, args: Vec<Expression>, ctx: &mut ConversionContext) -> ClarityExpression {
+    if matches!(&callee, Expression::Identifier(name) if name == "gasleft") {
+        return ctx.unsupported("gasleft()");
+    }
+    if matches!(&callee, Expression::Identifier(name) if name == "require") && !args.is_empty() {
+        return convert_require(args, ctx);
+    }
+    // `Token(addr)` only changes the Solidity type; the principal is the same
+    if matches!(&callee, Expression::Identifier(name) if ctx.peers.contains(name)) && args.len() == 1 {
+        return convert_expression(args.into_iter().next().unwrap(), ctx);
+    }
+    // Conversions such as `payable(to)` leave the principal unchanged, so a
+    // method called on one is called on the value itself
+    let callee = match callee {
+        Expression::MemberAccess(receiver, method) => match *receiver {
+            Expression::Cast(_, inner) if !matches!(*inner, Expression::Identifier(ref name) if name == "this") => {
+                Expression::MemberAccess(inner, method)
+            }
+            receiver => Expression::MemberAccess(Box::new(receiver), method),
+        },
+        callee => callee,
+    };
+    if let Expression::MemberAccess(receiver, method) = &callee {
+        if matches!(receiver.as_ref(), Expression::Identifier(object) if object == "abi") && method == "decode" {
+            return convert_abi_decode(args, ctx);
+        }
+        if let Some(library) = ctx.bound_library(receiver, method) {
+            return convert_library_call(&library, (**receiver).clone(), method, args, ctx);
+        }
+        // A trait-typed parameter names the contract to call at runtime
+        if let Expression::Identifier(name) = receiver.as_ref() {
+            if ctx.local_types.get(name).is_some_and(|t| ctx.interfaces.contains(t)) {
+                let mut call_args = vec![
+                    convert_expression((**receiver).clone(), ctx),
+                    ClarityExpression::Var(clarity_name(method, FUNCTION_SUFFIX, ctx.options.naming)),
+                ];
+                call_args.extend(args.into_iter().map(|arg| convert_expression(arg, ctx)));
+                return ClarityExpression::FunctionCall("contract-call?".to_string(), call_args);
             }
         }
+        // Clarity calls a known contract statically, by its deployed name,
+        // whatever address the Solidity variable holds
+        if let Some(peer) = ctx.called_peer(receiver) {
+            let mut call_args = vec![
+                ClarityExpression::Var(format!(".{}", peer.to_lowercase())),
+                ClarityExpression::Var(clarity_name(method, FUNCTION_SUFFIX, ctx.options.naming)),
+            ];
+            call_args.extend(args.into_iter().map(|arg| convert_expression(arg, ctx)));
+            return ClarityExpression::FunctionCall("contract-call?".to_string(), call_args);
+        }
+    }
+
+    let args: Vec<ClarityExpression> = args.into_iter().map(|arg| convert_expression(arg, ctx)).collect();
+
+    match callee {
+        Expression::MemberAccess(receiver, method) => match *receiver {
+            Expression::Identifier(contract) if !RESERVED_RECEIVERS.contains(&contract.as_str()) => {
+                let mut call_args = vec![
+                    ClarityExpression::Var(format!(".{}", contract)),
+                    ClarityExpression::Var(clarity_name(&method, FUNCTION_SUFFIX, ctx.options.naming)),
+                ];
+                call_args.extend(args);
+                ClarityExpression::FunctionCall("contract-call?".to_string(), call_args)
+            }
+            Expression::Identifier(object) if object == "abi" && method.starts_with("encode") => {
+                convert_abi_encode(&method, args, ctx)
+            }
+            receiver => {
+                let name = format!("{}-{}", receiver, method);
+                ClarityExpression::FunctionCall(name, args)
+            }
+        },
+        Expression::Identifier(name) if ctx.functions.contains(&name) => {
+            // A failing callee aborts the caller, as a revert would in Solidity
+            let call = ClarityExpression::FunctionCall(clarity_name(&name, FUNCTION_SUFFIX, ctx.options.naming), args);
+            ClarityExpression::FunctionCall("try!".to_string(), vec![call])
+        }
+        Expression::Identifier(name) => ClarityExpression::FunctionCall(clarity_name(&name, FUNCTION_SUFFIX, ctx.options.naming), args),
+        other => ClarityExpression::FunctionCall(other.to_string(), args),
+    }
+}
+
+/// Library functions with a Clarity builtin equivalent, as
+/// `(library, function, builtin)`. Clarity arithmetic already aborts on
+/// overflow, underflow and division by zero, which is all SafeMath adds.
+const LIBRARY_BUILTINS: &[(&str, &str, &str)] = &[
+    ("SafeMath", "add", "+"),
+    ("SafeMath", "sub", "-"),
+    ("SafeMath", "mul", "*"),
+    ("SafeMath", "div", "/"),
+    ("SafeMath", "mod", "mod"),
+];
+
+/// The Clarity builtin `library.function` is converted to, if it has one.
+pub(crate) fn library_builtin(library: &str, function: &str) -> Option<&'static str> {
+    LIBRARY_BUILTINS.iter()
+        .find(|(name, f, _)| *name == library && *f == function)
+        .map(|(_, _, builtin)| *builtin)
+}
+
+/// Lowers `value.method(args)` on a value bound to `library` by a `using`
+/// directive: known functions become their builtin, and anything else a
+/// call to the library function inlined into the contract, with the value
+/// as first argument.
+fn convert_library_call(
+    library: &str,
+    receiver: Expression,
+    method: &str,
+    args: Vec<Expression>,
+    ctx: &mut ConversionContext,
+) -> ClarityExpression {
+    let operands: Vec<ClarityExpression> = std::iter::once(receiver)
+        .chain(args)
+        .map(|operand| convert_operand(operand, ctx))
+        .collect();
+    if let Some(builtin) = library_builtin(library, method) {
+        return ClarityExpression::FunctionCall(builtin.to_string(), operands);
+    }
+    if !ctx.library_functions.iter().any(|(name, function)| name == library && function == method) {
+        ctx.error(format!(
+            "{}.{} in {} has no Clarity builtin and library {} is not in the source, so it cannot be inlined",
+            library, method, ctx.function_name, library
+        ));
+    }
+    // Like the contract's own functions, it aborts the caller on failure
+    let call = ClarityExpression::FunctionCall(clarity_name(method, FUNCTION_SUFFIX, ctx.options.naming), operands);
+    ClarityExpression::FunctionCall("try!".to_string(), vec![call])
+}
+
+/// Lowers `abi.encode(...)` and `abi.encodePacked(...)`, which usually feed
+/// a hash builtin such as `keccak256` or `sha256`.
+///
+/// `abi.encode` becomes the value's consensus serialization. Clarity has no
+/// packed encoding, so `abi.encodePacked` of a single value is dropped and
+/// the value hashed directly; either way the digest differs from Solidity's.
+fn convert_abi_encode(method: &str, mut args: Vec<ClarityExpression>, ctx: &mut ConversionContext) -> ClarityExpression {
+    let message = format!("abi.{} in {} is not byte-compatible with Solidity; hashes will differ", method, ctx.function_name);
+    ctx.report(Severity::Warning, message, None);
+
+    if method == "encodePacked" && args.len() == 1 {
+        return args.remove(0);
+    }
+    ctx.require_version("to-consensus-buff?", ClarityVersion::Clarity2);
+    let value = if args.len() == 1 {
+        args.remove(0)
+    } else {
+        ClarityExpression::Tuple(args.into_iter()
+            .enumerate()
+            .map(|(i, arg)| (format!("value-{}", i), arg))
+            .collect())
+    };
+    ClarityExpression::FunctionCall(
+        "unwrap-panic".to_string(),
+        vec![ClarityExpression::FunctionCall("to-consensus-buff?".to_string(), vec![value])]
+    )
+}
+
+/// Lowers `abi.decode(data, (T))` to `from-consensus-buff?`, which expects
+/// `data` to hold a Clarity consensus serialization rather than ABI bytes.
+fn convert_abi_decode(args: Vec<Expression>, ctx: &mut ConversionContext) -> ClarityExpression {
+    ctx.require_version("from-consensus-buff?", ClarityVersion::Clarity2);
+    let mut args = args.into_iter();
+    let data = args.next().map(|data| convert_expression(data, ctx));
+    let decoded_type = match args.next() {
+        Some(Expression::Identifier(t)) => ctx.convert_type(&t),
+        Some(Expression::Tuple(types)) => ClarityType::Tuple(types.iter()
+            .enumerate()
+            .map(|(i, t)| (format!("value-{}", i), ctx.convert_type(&t.to_string())))
+            .collect()),
+        _ => ClarityType::Uint,
+    };
+
+    let mut decode_args = vec![ClarityExpression::Var(decoded_type.to_string())];
+    decode_args.extend(data);
+    ClarityExpression::FunctionCall(
+        "unwrap-panic".to_string(),
+        vec![ClarityExpression::FunctionCall("from-consensus-buff?".to_string(), decode_args)]
+    )
+}
+
+/// Evaluates arithmetic over two uint literals at transpile time.
+///
+/// Returns `None` when either side is not a literal or the result would
+/// overflow, underflow or divide by zero, leaving the expression unfolded.
+fn fold_constant(op: &str, left: &ClarityExpression, right: &ClarityExpression) -> Option<u128> {
+    let uint = |expr: &ClarityExpression| match expr {
+        ClarityExpression::Literal(val) => val.strip_prefix('u')?.parse::<u128>().ok(),
+        _ => None,
+    };
+    let (left, right) = (uint(left)?, uint(right)?);
+
+    match op {
+        "+" => left.checked_add(right),
+        "-" => left.checked_sub(right),
+        "*" => left.checked_mul(right),
+        "/" => left.checked_div(right),
+        _ => None,
     }
 }
 
+/// Operators backed by the `bit-*` builtins added in Clarity 2.
+const BITWISE_OPERATORS: &[&str] = &["&", "|", "^", "<<", ">>"];
+
+/// Maps a Solidity binary operator onto the name of the Clarity function.
+fn convert_operator(op: &str) -> String {
+    match op {
+        "==" => "is-eq",
+        "&&" => "and",
+        "||" => "or",
+        "%" => "mod",
+        "&" => "bit-and",
+        "|" => "bit-or",
+        "^" => "bit-xor",
+        "<<" => "bit-shift-left",
+        ">>" => "bit-shift-right",
+        _ => op,
+    }.to_string()
+}
+
 impl std::fmt::Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -319,6 +2234,23 @@ impl std::fmt::Display for Expression {
             Expression::BinaryOp(left, op, right) => write!(f, "({} {} {})", left, op, right),
             Expression::MapAccess(map, key) => write!(f, "{}[{}]", map, key),
             Expression::MemberAccess(expr, member) => write!(f, "{}.{}", expr, member),
+            Expression::UnaryOp(op, operand) => write!(f, "{}{}", op, operand),
+            Expression::FunctionCall(callee, args) => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", callee, args.join(", "))
+            }
+            Expression::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "({})", items.join(", "))
+            }
+            Expression::ArrayLiteral(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Expression::Cast(target_type, inner) => write!(f, "{}({})", target_type, inner),
+            Expression::Ternary(cond, then_expr, else_expr) => {
+                write!(f, "({} ? {} : {})", cond, then_expr, else_expr)
+            }
         }
     }
 }
\ No newline at end of file
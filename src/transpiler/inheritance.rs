@@ -0,0 +1,140 @@
+use super::ast::*;
+use super::warning::{Severity, Warning};
+
+/// Merges the members of each contract's base contracts into it.
+///
+/// Bases are resolved against the other contracts in the same source and
+/// linearized depth-first in declaration order, so members defined later
+/// (and finally the derived contract's own members) override earlier ones
+/// with the same name. Bases that cannot be found are skipped and reported
+/// in the derived contract's `warnings`. Function declarations without a body, such as those of an
+/// implemented interface, are not inherited: only an interface keeps them,
+/// to define its trait.
+///
+/// Constructors are chained rather than overridden: base constructor bodies
+/// run first, with parameters bound to the arguments given in the derived
+/// constructor's header. Base parameters given no arguments become
+/// parameters of the merged constructor.
+pub fn resolve_inheritance(contracts: Vec<Contract>) -> Vec<Contract> {
+    contracts.iter()
+        .map(|contract| {
+            let mut merged = empty_like(contract);
+            let mut visiting = Vec::new();
+            merge_into(&mut merged, contract, &contracts, &mut visiting);
+            merged
+        })
+        .collect()
+}
+
+fn empty_like(contract: &Contract) -> Contract {
+    Contract {
+        name: contract.name.clone(),
+        functions: Vec::new(),
+        state_variables: Vec::new(),
+        events: Vec::new(),
+        constructor: None,
+        base_contracts: contract.base_contracts.clone(),
+        is_interface: contract.is_interface,
+        is_library: contract.is_library,
+        structs: Vec::new(),
+        enums: Vec::new(),
+        using: Vec::new(),
+        errors: Vec::new(),
+        unsupported: Vec::new(),
+        warnings: Vec::new(),
+    }
+}
+
+fn warn(warnings: &mut Vec<Warning>, message: String) {
+    warnings.push(Warning { severity: Severity::Warning, message, line: None });
+}
+
+fn merge_into(merged: &mut Contract, contract: &Contract, all: &[Contract], visiting: &mut Vec<String>) {
+    visiting.push(contract.name.clone());
+
+    for base_name in &contract.base_contracts {
+        if visiting.contains(base_name) {
+            warn(&mut merged.warnings, format!("cyclic inheritance between {} and {}", contract.name, base_name));
+            continue;
+        }
+        match all.iter().find(|c| &c.name == base_name) {
+            Some(base) => merge_into(merged, base, all, visiting),
+            None => warn(&mut merged.warnings, format!(
+                "base contract {} of {} not found, inherited members are skipped",
+                base_name, contract.name
+            )),
+        }
+    }
+
+    for item in &contract.structs {
+        merged.structs.retain(|s| s.name != item.name);
+        merged.structs.push(item.clone());
+    }
+    for item in &contract.enums {
+        merged.enums.retain(|e| e.name != item.name);
+        merged.enums.push(item.clone());
+    }
+    for error in &contract.errors {
+        merged.errors.retain(|e| e.name != error.name);
+        merged.errors.push(error.clone());
+    }
+    merged.using.extend(contract.using.iter().cloned());
+    merged.unsupported.extend(contract.unsupported.iter().cloned());
+    for var in &contract.state_variables {
+        merged.state_variables.retain(|v| v.name != var.name);
+        merged.state_variables.push(var.clone());
+    }
+    for func in contract.functions.iter().filter(|f| f.implemented || merged.is_interface) {
+        merged.functions.retain(|f| f.name != func.name);
+        merged.functions.push(func.clone());
+    }
+    for event in &contract.events {
+        merged.events.retain(|e| e.name != event.name);
+        merged.events.push(event.clone());
+    }
+    if let Some(constructor) = &contract.constructor {
+        let base = merged.constructor.take();
+        merged.constructor = Some(chain_constructor(base, constructor, contract, all, &mut merged.warnings));
+    }
+
+    visiting.pop();
+}
+
+fn chain_constructor(
+    base: Option<Constructor>,
+    derived: &Constructor,
+    contract: &Contract,
+    all: &[Contract],
+    warnings: &mut Vec<Warning>,
+) -> Constructor {
+    let mut bindings = Vec::new();
+    let mut bound = Vec::new();
+    for (base_name, args) in &derived.base_calls {
+        if !contract.base_contracts.contains(base_name) {
+            warn(warnings, format!(
+                "modifier {} on the constructor of {} is not supported and was skipped",
+                base_name, contract.name
+            ));
+            continue;
+        }
+        let params = all.iter()
+            .find(|c| &c.name == base_name)
+            .and_then(|c| c.constructor.as_ref())
+            .map(|c| c.params.as_slice())
+            .unwrap_or_default();
+        for (param, arg) in params.iter().zip(args) {
+            bindings.push(StatementKind::LocalVar(param.name.clone(), param.param_type.clone(), Some(arg.clone())).into());
+            bound.push(param.name.clone());
+        }
+    }
+
+    let mut chained = derived.clone();
+    chained.base_calls = Vec::new();
+    if let Some(base) = base {
+        chained.params.extend(base.params.into_iter().filter(|p| !bound.contains(&p.name)));
+        bindings.extend(base.body);
+    }
+    bindings.extend(std::mem::take(&mut chained.body));
+    chained.body = bindings;
+    chained
+}
@@ -1,9 +1,69 @@
 pub mod ast;
 pub mod converter;
+pub mod inheritance;
+pub mod optimize;
+pub mod options;
+pub mod types;
+pub mod warning;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use ast::Contract;
+pub use inheritance::resolve_inheritance;
+pub use options::{ClarityVersion, NamingStyle, ReservedNamePolicy, TranspileOptions};
+pub use types::{normalize_type, ClarityType};
+pub use warning::{Severity, Warning};
 
-pub fn convert(contract: Contract) -> Result<converter::ClarityContract> {
-    converter::convert_contract(contract)
-}
\ No newline at end of file
+/// Converts contracts that are deployed together, in order. Calls from one
+/// to another become `contract-call?`s against the other's deployed name,
+/// and parameters typed with an interface take its trait. Libraries are not
+/// deployed; their functions are inlined into the contracts using them.
+pub fn convert(contracts: Vec<Contract>, options: &TranspileOptions) -> Result<Vec<converter::ClarityContract>> {
+    options.validate()?;
+    let (libraries, contracts): (Vec<Contract>, Vec<Contract>) = contracts.into_iter()
+        .partition(|contract| contract.is_library);
+    let contracts = contracts.into_iter()
+        .map(|contract| inline_libraries(contract, &libraries))
+        .collect::<Result<Vec<_>>>()?;
+    let (interfaces, peers): (Vec<&Contract>, Vec<&Contract>) = contracts.iter()
+        .partition(|contract| contract.is_interface);
+    let peers: Vec<String> = peers.iter().map(|contract| contract.name.clone()).collect();
+    let interfaces: Vec<String> = interfaces.iter().map(|contract| contract.name.clone()).collect();
+    contracts.into_iter()
+        .map(|contract| {
+            let name = contract.name.clone();
+            converter::convert_contract_with_peers(contract, &peers, &interfaces, options)
+                .with_context(|| format!("Failed to convert {} to Clarity", name))
+        })
+        .collect()
+}
+
+/// Adds the functions of each library `contract` binds with `using` to it as
+/// private functions, except those with a Clarity builtin equivalent.
+fn inline_libraries(mut contract: Contract, libraries: &[Contract]) -> Result<Contract> {
+    let mut inlined: Vec<&str> = Vec::new();
+    for directive in &contract.using {
+        let Some(library) = libraries.iter().find(|library| library.name == directive.library) else {
+            continue;
+        };
+        if inlined.contains(&library.name.as_str()) {
+            continue;
+        }
+        inlined.push(&library.name);
+        for func in &library.functions {
+            if converter::library_builtin(&library.name, &func.name).is_some() {
+                continue;
+            }
+            if contract.functions.iter().any(|f| f.name == func.name) {
+                return Err(anyhow!(
+                    "Function {} of library {} clashes with a function of {}; rename one of them",
+                    func.name, library.name, contract.name
+                ));
+            }
+            let mut func = func.clone();
+            func.visibility = Some("private".to_string());
+            func.library = Some(library.name.clone());
+            contract.functions.push(func);
+        }
+    }
+    Ok(contract)
+}
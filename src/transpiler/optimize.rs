@@ -0,0 +1,184 @@
+use super::converter::ClarityExpression;
+
+/// Hoists map reads that occur more than once in a function body into a
+/// `let` wrapping the body, so each entry is fetched once.
+///
+/// Only reads that cannot change within the body are hoisted: the map must
+/// not be written anywhere in the body, and the key may only refer to
+/// parameters, literals and data vars the body does not set. Whole
+/// `(default-to d (map-get? ...))` reads are preferred over the bare
+/// `map-get?` inside them.
+pub fn hoist_repeated_reads(body: Vec<ClarityExpression>) -> Vec<ClarityExpression> {
+    let mut writes = Writes::default();
+    for expr in &body {
+        writes.collect(expr);
+    }
+
+    let mut candidates: Vec<(ClarityExpression, usize)> = Vec::new();
+    for expr in &body {
+        collect_reads(expr, &writes, &mut candidates);
+    }
+
+    let mut body = body;
+    let mut bindings = Vec::new();
+    for (read, count) in candidates {
+        // An earlier, larger read may already have absorbed this one
+        if count < 2 || count_occurrences(&body, &read) < 2 {
+            continue;
+        }
+        let map_name = match &read {
+            ClarityExpression::MapGet(map_name, _) => map_name,
+            ClarityExpression::FunctionCall(_, args) => match args.last() {
+                Some(ClarityExpression::MapGet(map_name, _)) => map_name,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let name = binding_name(map_name, &bindings);
+        for expr in &mut body {
+            replace(expr, &read, &name);
+        }
+        bindings.push((name, read));
+    }
+
+    if bindings.is_empty() {
+        body
+    } else {
+        vec![ClarityExpression::Let(bindings, body)]
+    }
+}
+
+/// Maps and data vars written somewhere in a body, and names it binds.
+#[derive(Default)]
+struct Writes {
+    maps: Vec<String>,
+    vars: Vec<String>,
+    locals: Vec<String>,
+}
+
+impl Writes {
+    fn collect(&mut self, expr: &ClarityExpression) {
+        match expr {
+            ClarityExpression::MapSet(map_name, keys, value) => {
+                self.maps.push(map_name.clone());
+                keys.iter().for_each(|key| self.collect(key));
+                self.collect(value);
+            }
+            ClarityExpression::FunctionCall(name, args) => {
+                match (name.as_str(), args.first()) {
+                    ("map-set" | "map-insert" | "map-delete", Some(ClarityExpression::Var(map_name))) => {
+                        self.maps.push(map_name.clone());
+                    }
+                    ("var-set", Some(ClarityExpression::Var(var_name))) => self.vars.push(var_name.clone()),
+                    _ => {}
+                }
+                args.iter().for_each(|arg| self.collect(arg));
+            }
+            ClarityExpression::Let(bindings, body) => {
+                for (name, value) in bindings {
+                    self.locals.push(name.clone());
+                    self.collect(value);
+                }
+                body.iter().for_each(|expr| self.collect(expr));
+            }
+            ClarityExpression::MapGet(_, args) | ClarityExpression::Print(args) => {
+                args.iter().for_each(|arg| self.collect(arg));
+            }
+            ClarityExpression::Tuple(fields) => fields.iter().for_each(|(_, value)| self.collect(value)),
+            ClarityExpression::Literal(_) | ClarityExpression::Var(_) => {}
+        }
+    }
+
+    fn is_stable_key(&self, key: &ClarityExpression) -> bool {
+        match key {
+            ClarityExpression::Literal(_) => true,
+            ClarityExpression::Var(name) => !self.locals.contains(name),
+            ClarityExpression::FunctionCall(name, args) if name == "var-get" => match args.as_slice() {
+                [ClarityExpression::Var(var_name)] => !self.vars.contains(var_name),
+                _ => false,
+            },
+            ClarityExpression::Tuple(fields) => fields.iter().all(|(_, value)| self.is_stable_key(value)),
+            _ => false,
+        }
+    }
+
+    fn is_stable_read(&self, expr: &ClarityExpression) -> bool {
+        match expr {
+            ClarityExpression::MapGet(map_name, keys) => {
+                !self.maps.contains(map_name) && keys.iter().all(|key| self.is_stable_key(key))
+            }
+            ClarityExpression::FunctionCall(name, args) if name == "default-to" => match args.as_slice() {
+                [ClarityExpression::Literal(_), read @ ClarityExpression::MapGet(..)] => self.is_stable_read(read),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Counts each stable read, outermost first.
+fn collect_reads(expr: &ClarityExpression, writes: &Writes, candidates: &mut Vec<(ClarityExpression, usize)>) {
+    if writes.is_stable_read(expr) {
+        match candidates.iter_mut().find(|(read, _)| read == expr) {
+            Some((_, count)) => *count += 1,
+            None => candidates.push((expr.clone(), 1)),
+        }
+    }
+    for child in children(expr) {
+        collect_reads(child, writes, candidates);
+    }
+}
+
+fn children(expr: &ClarityExpression) -> Vec<&ClarityExpression> {
+    match expr {
+        ClarityExpression::FunctionCall(_, args)
+        | ClarityExpression::MapGet(_, args)
+        | ClarityExpression::Print(args) => args.iter().collect(),
+        ClarityExpression::MapSet(_, keys, value) => keys.iter().chain(std::iter::once(value.as_ref())).collect(),
+        ClarityExpression::Tuple(fields) => fields.iter().map(|(_, value)| value).collect(),
+        ClarityExpression::Let(bindings, body) => bindings.iter().map(|(_, value)| value).chain(body).collect(),
+        ClarityExpression::Literal(_) | ClarityExpression::Var(_) => Vec::new(),
+    }
+}
+
+fn count_occurrences(body: &[ClarityExpression], read: &ClarityExpression) -> usize {
+    fn count(expr: &ClarityExpression, read: &ClarityExpression) -> usize {
+        if expr == read {
+            return 1;
+        }
+        children(expr).into_iter().map(|child| count(child, read)).sum()
+    }
+    body.iter().map(|expr| count(expr, read)).sum()
+}
+
+fn replace(expr: &mut ClarityExpression, read: &ClarityExpression, name: &str) {
+    if expr == read {
+        *expr = ClarityExpression::Var(name.to_string());
+        return;
+    }
+    match expr {
+        ClarityExpression::FunctionCall(_, args)
+        | ClarityExpression::MapGet(_, args)
+        | ClarityExpression::Print(args) => args.iter_mut().for_each(|arg| replace(arg, read, name)),
+        ClarityExpression::MapSet(_, keys, value) => {
+            keys.iter_mut().for_each(|key| replace(key, read, name));
+            replace(value, read, name);
+        }
+        ClarityExpression::Tuple(fields) => fields.iter_mut().for_each(|(_, value)| replace(value, read, name)),
+        ClarityExpression::Let(bindings, body) => {
+            bindings.iter_mut().for_each(|(_, value)| replace(value, read, name));
+            body.iter_mut().for_each(|expr| replace(expr, read, name));
+        }
+        ClarityExpression::Literal(_) | ClarityExpression::Var(_) => {}
+    }
+}
+
+/// `balances-entry`, then `balances-entry-2`, ... for further reads of the same map.
+fn binding_name(map_name: &str, bindings: &[(String, ClarityExpression)]) -> String {
+    let base = format!("{}-entry", map_name);
+    let taken = |name: &str| bindings.iter().any(|(bound, _)| bound == name);
+    if !taken(&base) {
+        return base;
+    }
+    (2..).map(|i| format!("{}-{}", base, i)).find(|name| !taken(name)).unwrap_or(base)
+}
@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+
+/// What to do with Solidity identifiers that are reserved words in Clarity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservedNamePolicy {
+    /// Suffix the name (`map` -> `map-var`, `list` -> `list-fn`).
+    Rename,
+    /// Fail the conversion.
+    Error,
+}
+
+/// Case convention for names in the generated Clarity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingStyle {
+    /// `getUserBalance` -> `get-user-balance`, the Clarity convention.
+    Kebab,
+    /// Keep the Solidity name as written.
+    Preserve,
+    /// `getUserBalance` -> `get_user_balance`.
+    Snake,
+}
+
+/// Clarity language version the generated code targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClarityVersion {
+    Clarity1,
+    Clarity2,
+    Clarity3,
+}
+
+impl ClarityVersion {
+    /// The version number, as written in a Clarinet manifest.
+    pub fn number(&self) -> u32 {
+        match self {
+            ClarityVersion::Clarity1 => 1,
+            ClarityVersion::Clarity2 => 2,
+            ClarityVersion::Clarity3 => 3,
+        }
+    }
+
+    /// The latest Stacks epoch that deploys contracts at this version.
+    pub fn epoch(&self) -> &'static str {
+        match self {
+            ClarityVersion::Clarity1 => "2.05",
+            ClarityVersion::Clarity2 => "2.5",
+            ClarityVersion::Clarity3 => "3.0",
+        }
+    }
+}
+
+/// Configuration shared by the converter and the generator.
+#[derive(Debug, Clone)]
+pub struct TranspileOptions {
+    /// Emit `;; @desc` style documentation comments in the generated code.
+    pub emit_comments: bool,
+    /// Maximum length used for Solidity `string` values.
+    pub max_string_length: u32,
+    /// Map `string` to `string-utf8` instead of `string-ascii`.
+    pub utf8_strings: bool,
+    /// Guard the constructor's `init` function so it can only run once.
+    pub guard_init: bool,
+    /// Upper bound on iterations for loops lowered to `fold`.
+    pub max_iterations: u32,
+    /// Maximum length used for dynamic Solidity arrays (`uint256[]`).
+    pub max_list_length: u32,
+    /// Handling of identifiers that collide with Clarity reserved words.
+    pub reserved_names: ReservedNamePolicy,
+    /// Return the raw `optional` from map getters instead of unwrapping it
+    /// with `default-to` and the value type's zero value.
+    pub optional_map_getters: bool,
+    /// Return `map-get?`'s optional from functions ending in `return m[k];`,
+    /// so callers can tell a missing entry from a zero one, instead of
+    /// defaulting it to the value type's zero value.
+    pub preserve_map_optionals: bool,
+    /// Compile `m[k] != 0` and `m[k] == 0` (or `address(0)`) to `is-some`
+    /// and `is-none` on the entry. An entry explicitly set to zero then
+    /// counts as present, so this is off by default and the stored value
+    /// is compared instead.
+    pub membership_checks: bool,
+    /// Wrap the values returned by generated getters in `(ok ...)`. When
+    /// off, getters return the raw value, which other read-only functions
+    /// can use directly.
+    pub getters_return_response: bool,
+    /// Print events as `{topic: "event", name: "Transfer", data: {...}}`
+    /// instead of a flat tuple of the name and fields, so indexers can
+    /// pick them out by shape.
+    pub event_envelope: bool,
+    /// Clarity version whose builtins the generated code may use.
+    pub clarity_version: ClarityVersion,
+    /// Case convention applied to functions, parameters, variables and maps.
+    pub naming: NamingStyle,
+    /// Name of the Solidity source file, recorded in the generated header.
+    pub source_name: Option<String>,
+    /// Record the generation time in the header. Off by default so that
+    /// output is reproducible.
+    pub header_timestamp: bool,
+    /// Fail on Solidity globals with no Clarity equivalent (`gasleft()`,
+    /// `tx.gasprice`) and on unsupported constructs such as inline assembly,
+    /// instead of approximating or skipping them with a warning.
+    pub strict: bool,
+    /// Sort definitions alphabetically within each output section instead of
+    /// keeping source order, so reordering the Solidity leaves the output unchanged.
+    pub sort_definitions: bool,
+    /// Guard public functions against a zero `uint` parameter named like an
+    /// amount or value (`amount`, `amountIn`, `value`).
+    pub validate_inputs: bool,
+    /// Bind map reads repeated within a function once with `let`.
+    pub optimize: bool,
+    /// Emit public, then read-only, then private functions, each under a
+    /// banner comment, instead of in source order.
+    pub group_by_visibility: bool,
+    /// Spaces per nesting level in generated function bodies.
+    pub indent: usize,
+}
+
+impl Default for TranspileOptions {
+    fn default() -> Self {
+        TranspileOptions {
+            emit_comments: true,
+            max_string_length: 256,
+            utf8_strings: false,
+            guard_init: false,
+            max_iterations: 32,
+            max_list_length: 256,
+            reserved_names: ReservedNamePolicy::Rename,
+            optional_map_getters: false,
+            preserve_map_optionals: false,
+            membership_checks: false,
+            getters_return_response: true,
+            event_envelope: false,
+            clarity_version: ClarityVersion::Clarity2,
+            naming: NamingStyle::Kebab,
+            source_name: None,
+            header_timestamp: false,
+            strict: false,
+            sort_definitions: false,
+            validate_inputs: false,
+            optimize: false,
+            group_by_visibility: false,
+            indent: 2,
+        }
+    }
+}
+
+impl TranspileOptions {
+    /// Rejects settings the converter cannot honor.
+    pub fn validate(&self) -> Result<()> {
+        // A loop bounded to no iterations would fold over an empty list
+        if self.max_iterations == 0 {
+            return Err(anyhow!("max_iterations must be at least 1"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use super::options::TranspileOptions;
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+/// A Clarity type, as converted from a Solidity type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClarityType {
+    Uint,
+    Int,
+    Bool,
+    Principal,
+    Buff(u32),
+    StringAscii(u32),
+    StringUtf8(u32),
+    List(u32, Box<ClarityType>),
+    /// Rendered in the `{name: type, ...}` shorthand.
+    Tuple(Vec<(String, ClarityType)>),
+    Optional(Box<ClarityType>),
+    Response(Box<ClarityType>, Box<ClarityType>),
+    /// A trait reference, taken by parameters typed with an interface.
+    Trait(String),
+    /// A name that is not an elementary Solidity type, such as a struct,
+    /// enum or contract type. Callers resolve or report it.
+    Unknown(String),
+}
+
+impl fmt::Display for ClarityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClarityType::Uint => write!(f, "uint"),
+            ClarityType::Int => write!(f, "int"),
+            ClarityType::Bool => write!(f, "bool"),
+            ClarityType::Principal => write!(f, "principal"),
+            ClarityType::Buff(len) => write!(f, "(buff {})", len),
+            ClarityType::StringAscii(len) => write!(f, "(string-ascii {})", len),
+            ClarityType::StringUtf8(len) => write!(f, "(string-utf8 {})", len),
+            ClarityType::List(len, element) => write!(f, "(list {} {})", len, element),
+            ClarityType::Tuple(fields) => {
+                let fields: Vec<String> = fields.iter()
+                    .map(|(name, field_type)| format!("{}: {}", name, field_type))
+                    .collect();
+                write!(f, "{{{}}}", fields.join(", "))
+            }
+            ClarityType::Optional(inner) => write!(f, "(optional {})", inner),
+            ClarityType::Response(ok, err) => write!(f, "(response {} {})", ok, err),
+            ClarityType::Trait(alias) => write!(f, "<{}>", alias),
+            ClarityType::Unknown(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Converts a Solidity type name to its Clarity type.
+///
+/// Integers of every width map to `uint`/`int`, since Clarity has a single
+/// 128-bit width of each; `bytesN` maps to `(buff N)`, and dynamic `bytes`,
+/// strings and arrays are bounded by the lengths in `options`. Names that
+/// are not elementary types come back as [`ClarityType::Unknown`]. Malformed
+/// elementary names, such as `uint7` or `bytes33`, are errors.
+pub fn normalize_type(solidity_type: &str, options: &TranspileOptions) -> Result<ClarityType> {
+    if let Some(inner) = solidity_type.strip_suffix(']') {
+        let (element, size) = inner.rsplit_once('[')
+            .ok_or_else(|| anyhow!("Malformed array type {}", solidity_type))?;
+        let size = match size {
+            "" => options.max_list_length,
+            size => size.parse()
+                .map_err(|_| anyhow!("Invalid array length in {}", solidity_type))?,
+        };
+        return Ok(ClarityType::List(size, Box::new(normalize_type(element, options)?)));
+    }
+
+    match solidity_type {
+        "bool" => return Ok(ClarityType::Bool),
+        "address" | "address payable" => return Ok(ClarityType::Principal),
+        "string" if options.utf8_strings => return Ok(ClarityType::StringUtf8(options.max_string_length)),
+        "string" => return Ok(ClarityType::StringAscii(options.max_string_length)),
+        "bytes" => return Ok(ClarityType::Buff(options.max_string_length)),
+        _ => {}
+    }
+
+    for (prefix, min, max, step) in [("uint", 8, 256, 8), ("int", 8, 256, 8), ("bytes", 1, 32, 1)] {
+        let Some(width) = solidity_type.strip_prefix(prefix) else { continue };
+        if !width.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if width.is_empty() {
+            return Ok(if prefix == "int" { ClarityType::Int } else { ClarityType::Uint });
+        }
+        let width: u32 = width.parse()
+            .map_err(|_| anyhow!("Invalid type {}", solidity_type))?;
+        if width < min || width > max || !width.is_multiple_of(step) {
+            return Err(anyhow!("Invalid type {}: {} takes a width from {} to {}", solidity_type, prefix, min, max));
+        }
+        return Ok(match prefix {
+            "uint" => ClarityType::Uint,
+            "int" => ClarityType::Int,
+            _ => ClarityType::Buff(width),
+        });
+    }
+
+    Ok(ClarityType::Unknown(solidity_type.to_string()))
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// How much attention a [`Warning`] needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The output behaves differently from the Solidity, or something was skipped.
+    Warning,
+    /// The output is faithful but callers have to adapt, e.g. to send STX explicitly.
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// A problem found while converting a contract that did not stop it from
+/// being transpiled, such as a skipped construct or a lossy conversion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub severity: Severity,
+    pub message: String,
+    /// Line in the Solidity source, when known.
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}: line {}: {}", self.severity.as_str(), line, self.message),
+            None => write!(f, "{}: {}", self.severity.as_str(), self.message),
+        }
+    }
+}
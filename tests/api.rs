@@ -0,0 +1,276 @@
+use anyhow::Result;
+use sol2clarity::{combine_contracts, transpile_file, transpile_source, Severity, TranspileOptions};
+use std::fs;
+
+#[test]
+fn test_transpile_source_returns_each_contract() -> Result<()> {
+    let source = r#"
+        contract Counter {
+            uint256 public count;
+            function increment() public {
+                count = count + 1;
+            }
+        }
+
+        contract Registry {
+            mapping(address => uint256) public entries;
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+
+    let names: Vec<&str> = outputs.iter().map(|output| output.name.as_str()).collect();
+    assert_eq!(names, vec!["Counter", "Registry"]);
+    assert!(outputs[0].code.contains("(define-public (increment)"));
+    assert!(outputs[1].code.contains("(define-map entries principal uint)"));
+    Ok(())
+}
+
+#[test]
+fn test_transpile_source_reports_parse_errors() {
+    let result = transpile_source("contract {", &TranspileOptions::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_header_records_provenance() -> Result<()> {
+    let source = r#"
+        pragma solidity ^0.8.0;
+
+        contract Counter {
+            uint256 public count;
+        }
+    "#;
+    let options = TranspileOptions {
+        source_name: Some("counter.sol".to_string()),
+        ..TranspileOptions::default()
+    };
+    let outputs = transpile_source(source, &options)?;
+    let header = &outputs[0].code;
+
+    assert!(header.starts_with(";; Contract: Counter\n"));
+    assert!(header.contains(";; Source: counter.sol\n"));
+    assert!(header.contains(";; Solidity: ^0.8.0\n"));
+    assert!(header.contains(&format!(";; Generated by sol2clarity {}\n", env!("CARGO_PKG_VERSION"))));
+    assert!(!header.contains("Generated at"));
+    Ok(())
+}
+
+#[test]
+fn test_transpile_file_resolves_relative_imports() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-imports-{}", std::process::id()));
+    fs::create_dir_all(dir.join("lib"))?;
+    fs::write(dir.join("lib/Base.sol"), r#"
+        contract Base {
+            uint256 public total;
+            function add(uint256 amount) public {
+                total = total + amount;
+            }
+        }
+    "#)?;
+    fs::write(dir.join("Main.sol"), r#"
+        import "./lib/Base.sol";
+        import "./lib/../lib/Base.sol";
+
+        contract Main is Base {
+            address owner;
+        }
+    "#)?;
+    let outputs = transpile_file(&dir.join("Main.sol"), &TranspileOptions::default());
+    fs::remove_dir_all(&dir)?;
+    let outputs = outputs?;
+
+    let names: Vec<&str> = outputs.iter().map(|output| output.name.as_str()).collect();
+    assert_eq!(names, vec!["Main"]);
+    assert!(outputs[0].code.contains("(define-data-var total uint u0)"));
+    assert!(outputs[0].code.contains("(define-public (add"));
+    Ok(())
+}
+
+#[test]
+fn test_transpile_file_reads_fixture() -> Result<()> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/token.sol");
+    let outputs = transpile_file(&path, &TranspileOptions::default())?;
+    let names: Vec<&str> = outputs.iter().map(|output| output.name.as_str()).collect();
+    assert_eq!(names, vec!["Token"]);
+    Ok(())
+}
+
+#[test]
+fn test_transpile_file_errors_name_the_file() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-bad-file-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("Broken.sol"), "contract Broken {\n")?;
+    let result = transpile_file(&dir.join("Broken.sol"), &TranspileOptions::default());
+    fs::remove_dir_all(&dir)?;
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("Broken.sol"), "unexpected error: {:#}", err);
+    Ok(())
+}
+
+#[test]
+fn test_imported_library_is_inlined() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-import-library-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    fs::write(
+        dir.join("Math.sol"),
+        "library Math {\n    function square(uint256 a) internal pure returns (uint256) { return a * a; }\n}\n",
+    )?;
+    fs::write(
+        dir.join("Calc.sol"),
+        "import \"./Math.sol\";\ncontract Calc {\n    using Math for uint256;\n    \
+         function area(uint256 side) public pure returns (uint256) { return side.square(); }\n}\n",
+    )?;
+    let result = transpile_file(&dir.join("Calc.sol"), &TranspileOptions::default());
+    fs::remove_dir_all(&dir)?;
+
+    let outputs = result?;
+    assert_eq!(outputs.len(), 1);
+    assert!(outputs[0].code.contains("(define-private (square (a uint))"));
+    Ok(())
+}
+
+#[test]
+fn test_transpile_file_reports_import_cycles() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-import-cycle-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("A.sol"), "import \"./B.sol\";\ncontract A {}\n")?;
+    fs::write(dir.join("B.sol"), "import \"./A.sol\";\ncontract B {}\n")?;
+    let result = transpile_file(&dir.join("A.sol"), &TranspileOptions::default());
+    fs::remove_dir_all(&dir)?;
+
+    let err = result.unwrap_err();
+    assert!(format!("{:#}", err).contains("Import cycle"), "unexpected error: {:#}", err);
+    Ok(())
+}
+
+#[test]
+fn test_combine_contracts_rejects_name_collisions() -> Result<()> {
+    let source = r#"
+        contract First {
+            uint256 count;
+            function increment() public { count = count + 1; }
+        }
+
+        contract Second {
+            uint256 total;
+            function increment() public { total = total + 1; }
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+    let err = combine_contracts("both", outputs).unwrap_err();
+    assert!(err.to_string().contains("increment (in First and Second)"), "unexpected error: {}", err);
+    Ok(())
+}
+
+#[test]
+fn test_combine_contracts_rejects_calls_between_them() -> Result<()> {
+    let source = r#"
+        contract Counter {
+            uint256 count;
+            function increment() public { count = count + 1; }
+        }
+
+        contract Caller {
+            function poke() public { Counter.increment(); }
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+    let err = combine_contracts("both", outputs).unwrap_err();
+    assert!(err.to_string().contains("Caller calls Counter"), "unexpected error: {}", err);
+    Ok(())
+}
+
+#[test]
+fn test_combine_contracts_renumbers_shared_error_codes() -> Result<()> {
+    let source = r#"
+        contract First {
+            function check(uint256 x) public { require(x > 0, "zero"); }
+        }
+
+        contract Second {
+            function bound(uint256 y) public { require(y < 10, "too large"); }
+        }
+    "#;
+    // Each contract numbers its own codes from u100
+    let combined = combine_contracts("both", transpile_source(source, &TranspileOptions::default())?)?;
+    assert_eq!(combined.name, "both");
+    assert!(combined.code.contains("(define-constant ERR_ZERO u100)"));
+    assert!(combined.code.contains("(define-constant ERR_TOO_LARGE u101)"));
+    assert_eq!(combined.error_codes, vec![(100, "zero".to_string()), (101, "too large".to_string())]);
+    Ok(())
+}
+
+#[test]
+fn test_zero_max_iterations_is_rejected() {
+    let options = TranspileOptions { max_iterations: 0, ..TranspileOptions::default() };
+    let err = transpile_source("contract Empty {}", &options).unwrap_err();
+    assert!(format!("{:#}", err).contains("max_iterations must be at least 1"), "unexpected error: {:#}", err);
+}
+
+#[test]
+fn test_lossy_conversion_is_a_structured_warning() -> Result<()> {
+    let source = r#"
+        contract Packer {
+            uint256 total;
+            function pack() public view returns (uint256) {
+                return uint8(total);
+            }
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+    let warnings = &outputs[0].warnings;
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert_eq!(warnings[0].message, "uint8 cast in pack is not range-checked");
+    assert_eq!(warnings[0].line, None);
+    Ok(())
+}
+
+#[test]
+fn test_import_and_inheritance_problems_are_structured_warnings() -> Result<()> {
+    let source = r#"
+        import "@openzeppelin/contracts/access/Ownable.sol";
+
+        contract Vault is Ownable {
+            uint256 total;
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+    let messages: Vec<&str> = outputs[0].warnings.iter().map(|w| w.message.as_str()).collect();
+    assert_eq!(messages, vec![
+        "import \"@openzeppelin/contracts/access/Ownable.sol\" is not resolved; only contracts in this source are transpiled",
+        "base contract Ownable of Vault not found, inherited members are skipped",
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_calls_between_contracts_in_one_file() -> Result<()> {
+    let source = r#"
+        contract Token {
+            uint256 total;
+            function mint(address to, uint256 amount) public {
+                total += amount;
+            }
+        }
+
+        contract Sale {
+            Token token;
+            function buy() public {
+                token.mint(msg.sender, 1);
+            }
+            function buyFrom(address seller) public {
+                Token(seller).mint(msg.sender, 2);
+            }
+        }
+    "#;
+    let outputs = transpile_source(source, &TranspileOptions::default())?;
+
+    let sale = &outputs[1].code;
+    assert!(sale.contains("(define-data-var token principal tx-sender)"));
+    assert!(sale.contains("(try! (contract-call? .token mint tx-sender u1))"));
+    assert!(sale.contains("(try! (contract-call? .token mint tx-sender u2))"));
+    assert!(!sale.contains("use-trait"));
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Runs `clarinet check` over the transpiled fixtures in `tests/fixtures/`.
+//!
+//! Opt-in: requires the `clarinet` feature and the `clarinet` binary on PATH.
+//!
+//!     cargo test --features clarinet --test clarinet_check
+#![cfg(feature = "clarinet")]
+
+use anyhow::{anyhow, Context, Result};
+use sol2clarity::{transpile_source, TranspileOptions};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_fixtures_pass_clarinet_check() -> Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let out_dir = std::env::temp_dir().join(format!("sol2clarity-check-{}", std::process::id()));
+    fs::create_dir_all(&out_dir)?;
+
+    let mut failures = Vec::new();
+    for entry in fs::read_dir(&fixtures)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "sol") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        for contract in transpile_source(&source, &TranspileOptions::default())? {
+            let clar_file = out_dir.join(format!("{}.clar", contract.name.to_lowercase()));
+            fs::write(&clar_file, contract.code)?;
+
+            let output = Command::new("clarinet")
+                .arg("check")
+                .arg(&clar_file)
+                .output()
+                .with_context(|| "Failed to run clarinet; is it on PATH?")?;
+            if !output.status.success() {
+                failures.push(format!(
+                    "{} ({}):\n{}{}",
+                    contract.name,
+                    path.display(),
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+        }
+    }
+
+    fs::remove_dir_all(&out_dir)?;
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("clarinet check failed:\n{}", failures.join("\n")))
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_dry_run_writes_no_files() -> Result<()> {
+    let out_dir = std::env::temp_dir().join(format!("sol2clarity-dry-run-{}", std::process::id()));
+    fs::create_dir_all(&out_dir)?;
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/counter.sol");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("-o")
+        .arg(&out_dir)
+        .arg("--dry-run")
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let written = fs::read_dir(&out_dir)?.count();
+    fs::remove_dir_all(&out_dir)?;
+
+    assert!(output.status.success());
+    assert!(stdout.contains("would write"), "unexpected output: {}", stdout);
+    assert!(stdout.contains("counter.clar"));
+    assert_eq!(written, 0);
+    Ok(())
+}
+
+#[test]
+fn test_clarinet_dry_run_reports_project_files() -> Result<()> {
+    let out_dir = std::env::temp_dir().join(format!("sol2clarity-clarinet-dry-run-{}", std::process::id()));
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/counter.sol");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("-o")
+        .arg(&out_dir)
+        .arg("--clarinet")
+        .arg("--dry-run")
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(!out_dir.exists());
+    for file in ["contracts/counter.clar", "tests/counter_test.ts", "Clarinet.toml"] {
+        let line = format!("would write {} (", out_dir.join(file).display());
+        assert!(stdout.contains(&line), "no {} in output: {}", file, stdout);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_emit_manifest_maps_error_codes_to_messages() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-manifest-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("vault.sol");
+    fs::write(&input, r#"
+        contract Vault {
+            address owner;
+            mapping(address => uint256) balances;
+            function withdraw(uint256 amount) public {
+                require(msg.sender == owner, "not owner");
+                require(amount > 0);
+                require(balances[msg.sender] >= amount, "insufficient balance");
+                balances[msg.sender] = balances[msg.sender] - amount;
+            }
+        }
+    "#)?;
+    let manifest_path = dir.join("errors.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("-o")
+        .arg(&dir)
+        .arg("--emit-manifest")
+        .arg(&manifest_path)
+        .output()?;
+    let manifest = fs::read_to_string(&manifest_path);
+    fs::remove_dir_all(&dir)?;
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        manifest?,
+        "{\n  \"100\": \"not owner\",\n  \"1\": \"failed without a specific error\",\n  \"101\": \"insufficient balance\"\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_single_file_combines_contracts() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-single-file-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("pair.sol");
+    fs::write(&input, r#"
+        contract Counter {
+            uint256 count;
+            function increment() public { count = count + 1; }
+        }
+
+        contract Registry {
+            mapping(address => uint256) entries;
+        }
+    "#)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("-o")
+        .arg(&dir)
+        .arg("--single-file")
+        .arg("combined")
+        .output()?;
+    let written: Vec<String> = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<_, _>>()?;
+    let combined = fs::read_to_string(dir.join("combined.clar"));
+    fs::remove_dir_all(&dir)?;
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(written.iter().filter(|name| name.ends_with(".clar")).count(), 1);
+    let combined = combined?;
+    assert!(combined.contains(";; ---- Counter ----"));
+    assert!(combined.contains("(define-public (increment)"));
+    assert!(combined.contains(";; ---- Registry ----"));
+    assert!(combined.contains("(define-map entries principal uint)"));
+    Ok(())
+}
+
+#[test]
+fn test_warnings_as_json() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sol2clarity-warnings-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("hasher.sol");
+    fs::write(&input, r#"
+        contract Hasher {
+            function skip() public {
+                assembly { let x := 1 }
+            }
+        }
+    "#)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("-o")
+        .arg(&dir)
+        .arg("--warnings-as-json")
+        .output()?;
+    fs::remove_dir_all(&dir)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains(r#"{"contract": "Hasher", "severity": "warning", "line": 4, "message": "inline assembly in skip is not supported and was skipped"}"#),
+        "unexpected stderr: {}", stderr
+    );
+    Ok(())
+}
+
+#[test]
+fn test_selftest_needs_no_input() -> Result<()> {
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg("--selftest")
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("(define-public (increment)"));
+    assert!(stdout.ends_with("selftest passed\n"));
+    Ok(())
+}
+
+#[test]
+fn test_max_iterations_must_be_positive() -> Result<()> {
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/counter.sol");
+    let output = Command::new(env!("CARGO_BIN_EXE_sol2clarity"))
+        .arg(&input)
+        .arg("--max-iterations")
+        .arg("0")
+        .arg("--dry-run")
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Invalid --max-iterations: 0"), "unexpected stderr: {}", stderr);
+    Ok(())
+}
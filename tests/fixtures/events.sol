@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Registry {
+    mapping(address => uint256) public entries;
+
+    event Registered(address indexed owner, uint256 value);
+
+    function register(uint256 value) public {
+        entries[msg.sender] = value;
+        emit Registered(msg.sender, value);
+    }
+}
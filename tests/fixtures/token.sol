@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Token {
+    mapping(address => uint256) public balances;
+    uint256 public totalSupply;
+
+    function mint(uint256 amount) public {
+        totalSupply = totalSupply + amount;
+    }
+}
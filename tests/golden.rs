@@ -0,0 +1,49 @@
+//! Compares the transpiled output of each `tests/fixtures/*.sol` with the
+//! golden `*.clar` file next to it.
+//!
+//! After an intended change to the generated code, regenerate the goldens
+//! and review the diff:
+//!
+//!     UPDATE_GOLDENS=1 cargo test --test golden
+use anyhow::{anyhow, Context, Result};
+use sol2clarity::{transpile_source, TranspileOptions};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_fixtures_match_golden_files() -> Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let update = std::env::var_os("UPDATE_GOLDENS").is_some();
+
+    let mut mismatches = Vec::new();
+    for entry in fs::read_dir(&fixtures)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "sol") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        let actual: String = transpile_source(&source, &TranspileOptions::default())?
+            .into_iter()
+            .map(|output| output.code)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let golden = path.with_extension("clar");
+        if update {
+            fs::write(&golden, &actual)?;
+            continue;
+        }
+        let expected = fs::read_to_string(&golden)
+            .with_context(|| format!("Missing golden file {}; run with UPDATE_GOLDENS=1", golden.display()))?;
+        if actual != expected {
+            mismatches.push(format!("{}:\n--- expected\n{}\n--- actual\n{}", golden.display(), expected, actual));
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("generated Clarity differs from golden files:\n{}", mismatches.join("\n")))
+    }
+}
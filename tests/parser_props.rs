@@ -0,0 +1,111 @@
+//! Property tests feeding randomly generated, well-formed contracts to the
+//! parser.
+
+use proptest::prelude::*;
+use sol2clarity::parser;
+
+/// Words the grammar treats specially and that cannot be used as names.
+const KEYWORDS: &[&str] = &[
+    "address", "assembly", "bool", "calldata", "constant", "constructor", "contract", "delete",
+    "emit", "enum", "error", "event", "external", "fallback", "false", "function", "immutable",
+    "import", "indexed", "interface", "internal", "is", "mapping", "memory", "modifier",
+    "payable", "pragma", "private", "public", "pure", "receive", "return", "returns", "revert",
+    "storage", "string", "struct", "true", "try", "using", "view", "while",
+];
+
+fn identifier() -> impl Strategy<Value = String> {
+    "[a-zA-Z][a-zA-Z0-9_]{0,12}".prop_filter("keyword or type name", |name| {
+        let elementary = ["uint", "int", "bytes"].iter().any(|prefix| {
+            name.strip_prefix(prefix).is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit()))
+        });
+        !KEYWORDS.contains(&name.as_str()) && !elementary
+    })
+}
+
+fn elementary_type() -> impl Strategy<Value = &'static str> {
+    prop::sample::select(vec!["uint256", "uint8", "int256", "address", "bool", "string", "bytes32"])
+}
+
+fn state_variable() -> impl Strategy<Value = String> {
+    let basic = (elementary_type(), prop::option::of(Just("public")), identifier())
+        .prop_map(|(ty, visibility, name)| format!("{} {} {};", ty, visibility.unwrap_or(""), name));
+    let mapping = (elementary_type(), elementary_type(), identifier())
+        .prop_map(|(key, value, name)| format!("mapping({} => {}) public {};", key, value, name));
+    let nested = identifier()
+        .prop_map(|name| format!("mapping(address => mapping(uint256 => bool)) {};", name));
+    prop_oneof![basic, mapping, nested]
+}
+
+fn expression() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        any::<u32>().prop_map(|n| n.to_string()),
+        identifier(),
+        Just("msg.sender".to_string()),
+        Just("true".to_string()),
+    ];
+    leaf.prop_recursive(3, 12, 2, |inner| {
+        prop_oneof![
+            (inner.clone(), prop::sample::select(vec!["+", "-", "*", "==", "<", "&&"]), inner.clone())
+                .prop_map(|(l, op, r)| format!("{} {} {}", l, op, r)),
+            inner.clone().prop_map(|e| format!("({})", e)),
+            (identifier(), inner.clone()).prop_map(|(map, key)| format!("{}[{}]", map, key)),
+            (inner.clone(), inner.clone(), inner).prop_map(|(c, t, e)| format!("{} ? {} : {}", c, t, e)),
+        ]
+    })
+}
+
+fn statement() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (identifier(), expression()).prop_map(|(target, value)| format!("{} = {};", target, value)),
+        (identifier(), expression(), expression())
+            .prop_map(|(map, key, value)| format!("{}[{}] = {};", map, key, value)),
+        expression().prop_map(|value| format!("return {};", value)),
+        Just("return;".to_string()),
+        (expression(), expression()).prop_map(|(cond, value)| format!("while ({}) {{ x = {}; }}", cond, value)),
+    ]
+}
+
+fn function() -> impl Strategy<Value = String> {
+    (
+        identifier(),
+        prop::collection::vec((elementary_type(), identifier()), 0..3),
+        prop::sample::select(vec!["public", "external", "internal", "private"]),
+        prop::option::of(prop::sample::select(vec!["view", "pure"])),
+        prop::collection::vec(statement(), 0..5),
+    )
+        .prop_map(|(name, params, visibility, mutability, body)| {
+            let params: Vec<String> = params.iter().map(|(ty, name)| format!("{} {}", ty, name)).collect();
+            format!(
+                "function {}({}) {} {} {{\n{}\n}}",
+                name,
+                params.join(", "),
+                visibility,
+                mutability.unwrap_or(""),
+                body.join("\n")
+            )
+        })
+}
+
+fn contract() -> impl Strategy<Value = (String, String)> {
+    (
+        identifier(),
+        prop::collection::vec(state_variable(), 0..5),
+        prop::collection::vec(function(), 0..4),
+    )
+        .prop_map(|(name, vars, functions)| {
+            let source = format!("contract {} {{\n{}\n{}\n}}\n", name, vars.join("\n"), functions.join("\n"));
+            (name, source)
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn parse_all_accepts_generated_contracts((name, source) in contract()) {
+        let contracts = parser::parse_all(&source)
+            .map_err(|e| TestCaseError::fail(format!("{}\n{}", e, source)))?;
+        prop_assert_eq!(contracts.len(), 1);
+        prop_assert_eq!(&contracts[0].name, &name);
+    }
+}